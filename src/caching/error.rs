@@ -1,10 +1,19 @@
 use redb::{CommitError, DatabaseError, StorageError, TableError, TransactionError};
+use std::io;
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum CacherError {
     #[error("Could not create a default cache directory. Problem: {0}")]
     CreateDefaultCache(String),
+    #[error("Could not open cache at {}. Problem: {source}", path.display())]
+    Open {
+        path: PathBuf,
+        source: DatabaseError,
+    },
+    #[error("Cache at {} has entries which could not be deserialized: {keys:?}", path.display())]
+    CorruptEntries { path: PathBuf, keys: Vec<String> },
     #[error(transparent)]
     CacheDatabase(#[from] DatabaseError),
     #[error(transparent)]
@@ -15,4 +24,6 @@ pub enum CacherError {
     CacheTable(#[from] TableError),
     #[error(transparent)]
     CacheStorage(#[from] StorageError),
+    #[error(transparent)]
+    Io(#[from] io::Error),
 }
@@ -1,3 +1,5 @@
 pub mod error;
+#[cfg(test)]
+pub mod null_cacher;
 pub mod redb_cacher;
 pub mod traits;
@@ -0,0 +1,18 @@
+use crate::caching::error::CacherError;
+use crate::caching::traits::{Cacheable, Cacher};
+
+/// A Cacher which never stores anything, so every lookup is a cache miss.
+///
+/// This is intended for testing the logic of cached clients without touching disk.
+#[derive(Debug, Default)]
+pub(crate) struct NullCacher;
+
+impl<T: Cacheable> Cacher<T> for NullCacher {
+    fn get(&self, _query: &str) -> Result<Option<T>, CacherError> {
+        Ok(None)
+    }
+
+    fn insert(&self, _object_to_cache: T) -> Result<(), CacherError> {
+        Ok(())
+    }
+}
@@ -1,17 +1,50 @@
 use crate::caching::error::CacherError;
-use crate::caching::traits::Cacheable;
+use crate::caching::traits::{Cacheable, Cacher};
 use crate::hgnc::GeneDoc;
 use crate::hgvs::HgvsVariant;
 use directories::ProjectDirs;
+use log::warn;
 use redb::{
-    Database as RedbDatabase, Database, DatabaseError, ReadableDatabase, TableDefinition, TypeName,
-    Value,
+    Database, DatabaseError, ReadOnlyDatabase, ReadTransaction, ReadableDatabase, ReadableTable,
+    TableDefinition, TableError, TableHandle, TypeName, Value,
 };
 use std::any::type_name;
+use std::collections::HashMap;
+use std::env;
 use std::env::home_dir;
+use std::ffi::OsString;
 use std::fs;
 use std::marker::PhantomData;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+
+/// The environment variable which overrides the directory of default caches.
+const CACHE_DIR_ENV_VAR: &str = "PIVOT_CACHE_DIR";
+
+/// The schema version of the entries of each table in a cache, by table name.
+const SCHEMA_VERSION_TABLE: TableDefinition<&str, u32> = TableDefinition::new("schema_version");
+
+/// The cache databases which are currently open, by canonical cache file path.
+///
+/// redb does not allow a database file to be opened twice by the same process, so every cacher of a cache file
+/// (e.g. several clients using the same cache) shares its open database, and relies on its transactions instead.
+/// A database is closed once the last cacher using it is dropped.
+static OPEN_DATABASES: LazyLock<Mutex<HashMap<PathBuf, Arc<Database>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The path by which a cache file is known in OPEN_DATABASES, so that different paths to the same file share a database.
+fn canonical_cache_path(cache_file_path: &Path) -> PathBuf {
+    match (cache_file_path.parent(), cache_file_path.file_name()) {
+        (Some(parent), Some(file_name)) => fs::canonicalize(if parent.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            parent
+        })
+        .map(|parent| parent.join(file_name))
+        .unwrap_or_else(|_| cache_file_path.to_path_buf()),
+        _ => cache_file_path.to_path_buf(),
+    }
+}
 
 macro_rules! implement_value_for_local_type {
     ($type_name:ty) => {
@@ -41,24 +74,73 @@ macro_rules! implement_value_for_local_type {
     };
 }
 
+/// The serialized bytes of a cache entry of type T.
+///
+/// Tables of T can be read as tables of RawCacheEntry<T>, which allows find_cache_entry to deserialize entries fallibly,
+/// since Value::from_bytes can't return an error.
+#[derive(Debug)]
+struct RawCacheEntry<T>(PhantomData<T>);
+
+impl<T: Value> Value for RawCacheEntry<T> {
+    type SelfType<'a>
+        = &'a [u8]
+    where
+        Self: 'a;
+    type AsBytes<'a>
+        = &'a [u8]
+    where
+        Self: 'a;
+
+    fn fixed_width() -> Option<usize> {
+        T::fixed_width()
+    }
+
+    fn from_bytes<'a>(data: &'a [u8]) -> &'a [u8]
+    where
+        Self: 'a,
+    {
+        data
+    }
+
+    fn as_bytes<'a, 'b: 'a>(value: &'a &'b [u8]) -> &'a [u8]
+    where
+        Self: 'b,
+    {
+        value
+    }
+
+    fn type_name() -> TypeName {
+        T::type_name()
+    }
+}
+
 implement_value_for_local_type!(GeneDoc);
 
 implement_value_for_local_type!(HgvsVariant);
 
 impl Cacheable for HgvsVariant {
-    fn keys(&self) -> Vec<&str> {
-        vec![self.transcript_hgvs()]
+    const TABLE_NAME: &'static str = "hgvs_variant";
+    /// Version 2 added the assembly to the cache key.
+    const SCHEMA_VERSION: u32 = 2;
+
+    fn keys(&self) -> Vec<String> {
+        vec![HgvsVariant::cache_key(
+            self.assembly(),
+            self.transcript_hgvs(),
+        )]
     }
 }
 
 impl Cacheable for GeneDoc {
-    fn keys(&self) -> Vec<&str> {
+    const TABLE_NAME: &'static str = "gene_doc";
+
+    fn keys(&self) -> Vec<String> {
         let mut keys = vec![];
         if let Some(symbol) = self.symbol() {
-            keys.push(symbol);
+            keys.push(symbol.to_string());
         }
         if let Some(id) = self.hgnc_id() {
-            keys.push(id);
+            keys.push(id.to_string());
         }
         keys
     }
@@ -68,41 +150,116 @@ impl Cacheable for GeneDoc {
 /// the RedbCacher will be able to cache instances of T to a RedbDatabase at cache_file_path.
 ///
 /// NOTE: in the RedbDatabase, a single table will be automatically constructed for the type T.
+/// The table is named T::TABLE_NAME, unless a different name is given on construction.
 /// If the user would like to have multiple caches of type T, then a different file path would have to be used.
 #[derive(Debug)]
-pub(crate) struct RedbCacher<T: Cacheable> {
+pub struct RedbCacher<T: Cacheable> {
     cache_file_path: PathBuf,
+    table_name: String,
+    /// The cache database, once it has been opened. It is shared with every other cacher of the same file through OPEN_DATABASES.
+    database: Mutex<Option<Arc<Database>>>,
     _phantom: PhantomData<T>,
 }
 
-impl<T: Cacheable> Default for RedbCacher<T> {
-    fn default() -> Self {
+impl<T: Cacheable> RedbCacher<T> {
+    pub(crate) fn new(cache_file_path: PathBuf) -> Self {
+        RedbCacher::new_with_table_name(cache_file_path, T::TABLE_NAME)
+    }
+
+    pub(crate) fn new_with_table_name(
+        cache_file_path: PathBuf,
+        table_name: impl Into<String>,
+    ) -> Self {
+        RedbCacher {
+            cache_file_path,
+            table_name: table_name.into(),
+            database: Mutex::new(None),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Constructs a RedbCacher in the directory given by the PIVOT_CACHE_DIR environment variable, if it is set.
+    /// Otherwise, the cache directory of the OS (or the home directory, if that is not available) is used.
+    pub(crate) fn try_default() -> Result<Self, CacherError> {
+        Self::try_default_with_override(env::var_os(CACHE_DIR_ENV_VAR))
+    }
+
+    /// Constructs a RedbCacher in cache_dir_override if it is given, and otherwise in the cache directory of the OS.
+    fn try_default_with_override(
+        cache_dir_override: Option<OsString>,
+    ) -> Result<Self, CacherError> {
+        if let Some(cache_dir) = cache_dir_override {
+            return Self::in_dir(PathBuf::from(cache_dir));
+        }
+
         let pkg_name = env!("CARGO_PKG_NAME");
 
         let pivot_cache_dir = ProjectDirs::from("", "", pkg_name)
             .map(|project_dir| project_dir.cache_dir().to_path_buf())
             .or_else(|| home_dir().map(|home| home.join(pkg_name)))
-            .unwrap_or_else(|| panic!("Could not find cache directory or home directory."));
+            .ok_or_else(|| {
+                CacherError::CreateDefaultCache(
+                    "Could not find cache directory or home directory.".to_string(),
+                )
+            })?;
 
-        if !pivot_cache_dir.exists() {
-            fs::create_dir_all(&pivot_cache_dir)
-                .expect("Failed to create default cache directory.");
-        }
+        Self::in_dir(pivot_cache_dir)
+    }
 
-        RedbCacher::new(pivot_cache_dir.join(type_name::<T>()))
+    /// Constructs and initialises the default RedbCacher. If that is not possible (e.g. because the cache directory is read-only),
+    /// a cache in the temp directory is used instead and a warning is logged.
+    ///
+    /// Panics only if the cache in the temp directory can't be created either.
+    pub(crate) fn default_or_temp() -> Self {
+        Self::or_temp_dir(Self::try_default())
     }
-}
 
-impl<T: Cacheable> RedbCacher<T> {
-    pub(crate) fn new(cache_file_path: PathBuf) -> Self {
-        RedbCacher {
-            cache_file_path,
-            _phantom: PhantomData,
+    fn or_temp_dir(cacher: Result<Self, CacherError>) -> Self {
+        cacher
+            .and_then(|cacher| cacher.init_cache().map(|_| cacher))
+            .unwrap_or_else(|err| {
+                let temp_cache_dir = env::temp_dir().join(env!("CARGO_PKG_NAME"));
+                warn!(
+                    "Could not create default cache, so using {} instead: {}",
+                    temp_cache_dir.display(),
+                    err
+                );
+                Self::in_dir(temp_cache_dir)
+                    .and_then(|cacher| cacher.init_cache().map(|_| cacher))
+                    .expect("Could not create cache in temp directory.")
+            })
+    }
+
+    /// Constructs a RedbCacher whose cache file is in cache_dir, creating the directory if it does not exist.
+    pub(crate) fn in_dir(cache_dir: PathBuf) -> Result<Self, CacherError> {
+        if !cache_dir.exists() {
+            fs::create_dir_all(&cache_dir)?;
         }
+
+        let cache_file_path = cache_dir.join(T::TABLE_NAME);
+        // cache files used to be named after type_name::<T>(), which changes whenever T is moved
+        let legacy_cache_file_path = cache_dir.join(type_name::<T>());
+        if !cache_file_path.exists()
+            && legacy_cache_file_path.exists()
+            && let Err(err) = fs::rename(&legacy_cache_file_path, &cache_file_path)
+        {
+            warn!(
+                "Could not move cache {} to {}, so it will not be used: {}",
+                legacy_cache_file_path.display(),
+                cache_file_path.display(),
+                err
+            );
+        }
+
+        Ok(RedbCacher::new(cache_file_path))
     }
 
-    fn table_definition() -> TableDefinition<'static, &'static str, T> {
-        TableDefinition::new(type_name::<T>())
+    fn table_definition(&self) -> TableDefinition<'_, &'static str, T> {
+        TableDefinition::new(&self.table_name)
+    }
+
+    fn raw_table_definition(&self) -> TableDefinition<'_, &'static str, RawCacheEntry<T>> {
+        TableDefinition::new(&self.table_name)
     }
 
     pub(crate) fn cache_file_path(&self) -> &PathBuf {
@@ -110,28 +267,263 @@ impl<T: Cacheable> RedbCacher<T> {
     }
 
     pub(crate) fn init_cache(&self) -> Result<(), CacherError> {
-        let cache = RedbDatabase::create(self.cache_file_path.clone())?;
+        let cache = self.shared_database(true)?;
+
+        let write_txn = cache.begin_write()?;
+        {
+            write_txn.open_table(self.table_definition())?;
+        }
+        write_txn.commit()?;
+        self.migrate_legacy_table(&cache)?;
+        self.migrate(&cache)
+    }
+
+    /// Moves the entries of the table named after type_name::<T>(), which is how tables were named before T::TABLE_NAME,
+    /// into the table of the cacher and deletes it. Entries which are already in the table of the cacher are kept.
+    ///
+    /// The moved entries are then migrated like the entries of any older schema version.
+    fn migrate_legacy_table(&self, cache: &Database) -> Result<(), CacherError> {
+        let legacy_table_name = type_name::<T>();
+        if self.table_name != T::TABLE_NAME || self.table_name == legacy_table_name {
+            return Ok(());
+        }
+
+        let write_txn = cache.begin_write()?;
+        if !write_txn
+            .list_tables()?
+            .any(|table| table.name() == legacy_table_name)
+        {
+            return Ok(());
+        }
+        let legacy_table_definition: TableDefinition<&str, RawCacheEntry<T>> =
+            TableDefinition::new(legacy_table_name);
+        {
+            let legacy_table = write_txn.open_table(legacy_table_definition)?;
+            let mut table = write_txn.open_table(self.raw_table_definition())?;
+            for cache_entry in legacy_table.iter()? {
+                let (key, value) = cache_entry?;
+                if table.get(key.value())?.is_none() {
+                    table.insert(key.value(), value.value())?;
+                }
+            }
+            let mut version_table = write_txn.open_table(SCHEMA_VERSION_TABLE)?;
+            version_table.remove(self.table_name.as_str())?;
+        }
+        write_txn.delete_table(legacy_table_definition)?;
+        write_txn.commit()?;
+        Ok(())
+    }
 
+    /// Migrates the cache entries if they were cached with a different T::SCHEMA_VERSION (or before schema versions were recorded).
+    ///
+    /// Entries which can still be deserialized are cached again in their current form under their current keys,
+    /// and all other entries are removed.
+    pub(crate) fn migrate(&self, cache: &Database) -> Result<(), CacherError> {
         let write_txn = cache.begin_write()?;
         {
-            write_txn.open_table(Self::table_definition())?;
+            let mut version_table = write_txn.open_table(SCHEMA_VERSION_TABLE)?;
+            let cached_version = version_table
+                .get(self.table_name.as_str())?
+                .map(|version| version.value());
+            if cached_version == Some(T::SCHEMA_VERSION) {
+                return Ok(());
+            }
+
+            let mut table = write_txn.open_table(self.raw_table_definition())?;
+            let mut cache_entries = vec![];
+            for cache_entry in table.iter()? {
+                let (key, value) = cache_entry?;
+                cache_entries.push((key.value().to_string(), value.value().to_vec()));
+            }
+            for (key, value) in cache_entries {
+                let migrated_entry = serde_json::from_slice::<T>(&value).and_then(|object| {
+                    serde_json::to_vec(&object)
+                        .map(|migrated_value| (object.keys(), migrated_value))
+                });
+                match migrated_entry {
+                    Ok((migrated_keys, migrated_value)) => {
+                        if !migrated_keys.contains(&key) {
+                            table.remove(key.as_str())?;
+                        }
+                        for migrated_key in migrated_keys {
+                            table.insert(migrated_key.as_str(), migrated_value.as_slice())?;
+                        }
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Removing cache entry for {} in {} which could not be migrated: {}",
+                            key,
+                            self.cache_file_path.display(),
+                            err
+                        );
+                        table.remove(key.as_str())?;
+                    }
+                }
+            }
+            version_table.insert(self.table_name.as_str(), T::SCHEMA_VERSION)?;
         }
         write_txn.commit()?;
         Ok(())
     }
 
-    pub(crate) fn open_cache(&self) -> Result<RedbDatabase, DatabaseError> {
-        RedbDatabase::open(&self.cache_file_path)
+    /// Opens the cache, or shares it if it is already open (e.g. in another thread).
+    pub(crate) fn open_cache(&self) -> Result<Arc<Database>, CacherError> {
+        self.shared_database(false)
+    }
+
+    /// The open cache database, which is opened (or created, if create is true) if no cacher has opened it yet.
+    fn shared_database(&self, create: bool) -> Result<Arc<Database>, CacherError> {
+        let mut database = self.database.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(database) = database.as_ref() {
+            return Ok(database.clone());
+        }
+
+        let mut open_databases = OPEN_DATABASES
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let canonical_path = canonical_cache_path(&self.cache_file_path);
+        let opened_database = match open_databases.get(&canonical_path) {
+            Some(opened_database) => opened_database.clone(),
+            None => {
+                let opened_database = Arc::new(
+                    if create {
+                        Database::create(&self.cache_file_path)
+                    } else {
+                        Database::open(&self.cache_file_path)
+                    }
+                    .map_err(|source| self.open_error(source))?,
+                );
+                open_databases.insert(canonical_path, opened_database.clone());
+                opened_database
+            }
+        };
+        *database = Some(opened_database.clone());
+        Ok(opened_database)
+    }
+
+    /// The open cache database, if this or any other cacher of the same file has opened it.
+    fn opened_database(&self) -> Option<Arc<Database>> {
+        let database = self.database.lock().unwrap_or_else(PoisonError::into_inner);
+        database.clone().or_else(|| {
+            OPEN_DATABASES
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get(&canonical_cache_path(&self.cache_file_path))
+                .cloned()
+        })
+    }
+
+    /// Opens the cache read-only, so that it is neither created nor modified. Returns None if the cache file does not exist.
+    pub(crate) fn try_open(&self) -> Result<Option<ReadOnlyDatabase>, CacherError> {
+        if !self.cache_file_path.exists() {
+            return Ok(None);
+        }
+        ReadOnlyDatabase::open(&self.cache_file_path)
+            .map(Some)
+            .map_err(|source| self.open_error(source))
     }
+
+    /// Whether there is a readable cache entry for the key. A cache which does not exist yet contains nothing.
+    ///
+    /// If the cache is not open yet, it is only opened read-only.
+    pub(crate) fn contains(&self, key: &str) -> Result<bool, CacherError> {
+        let cache_reader = match self.opened_database() {
+            Some(cache) => cache.begin_read()?,
+            None => match self.try_open()? {
+                Some(cache) => cache.begin_read()?,
+                None => return Ok(false),
+            },
+        };
+        self.contains_in(key, &cache_reader)
+    }
+
+    fn contains_in(&self, key: &str, cache_reader: &ReadTransaction) -> Result<bool, CacherError> {
+        let table = match cache_reader.open_table(self.raw_table_definition()) {
+            Ok(table) => table,
+            Err(TableError::TableDoesNotExist(_)) => return Ok(false),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(table
+            .get(key)?
+            .is_some_and(|cache_entry| serde_json::from_slice::<T>(cache_entry.value()).is_ok()))
+    }
+
+    fn open_error(&self, source: DatabaseError) -> CacherError {
+        CacherError::Open {
+            path: self.cache_file_path.clone(),
+            source,
+        }
+    }
+    /// Entries which can't be deserialized (e.g. because they were cached by an older version of T) are treated as missing.
     pub(crate) fn find_cache_entry(&self, query: &str, cache: &Database) -> Option<T> {
         let cache_reader = cache.begin_read().ok()?;
-        let table = cache_reader.open_table(Self::table_definition()).ok()?;
+        let table = cache_reader.open_table(self.raw_table_definition()).ok()?;
+
+        let cache_entry = table.get(query).ok()??;
+        match serde_json::from_slice(cache_entry.value()) {
+            Ok(object) => Some(object),
+            Err(err) => {
+                warn!(
+                    "Ignoring cache entry for {} in {} which could not be deserialized: {}",
+                    query,
+                    self.cache_file_path.display(),
+                    err
+                );
+                None
+            }
+        }
+    }
+
+    /// Every key in the cache with its entry, in key order.
+    ///
+    /// Objects with several keys (e.g. a GeneDoc, which is cached by symbol and by HGNC ID) appear once for each key.
+    /// Entries which can't be deserialized are skipped, as in find_cache_entry.
+    pub(crate) fn entries(&self, cache: &Database) -> Result<Vec<(String, T)>, CacherError> {
+        let cache_reader = cache.begin_read()?;
+        let table = cache_reader.open_table(self.raw_table_definition())?;
+
+        let mut entries = vec![];
+        for cache_entry in table.iter()? {
+            let (key, value) = cache_entry?;
+            match serde_json::from_slice(value.value()) {
+                Ok(object) => entries.push((key.value().to_string(), object)),
+                Err(err) => warn!(
+                    "Ignoring cache entry for {} in {} which could not be deserialized: {}",
+                    key.value(),
+                    self.cache_file_path.display(),
+                    err
+                ),
+            }
+        }
+        Ok(entries)
+    }
 
-        if let Ok(Some(cache_entry)) = table.get(query) {
-            return Some(cache_entry.value().into());
+    /// Checks that the cache can be opened and that every entry in it can be deserialized, returning the number of entries.
+    ///
+    /// If any entry can't be deserialized, a CorruptEntries error lists their keys.
+    pub(crate) fn verify(&self) -> Result<usize, CacherError> {
+        let cache = self.open_cache()?;
+        let cache_reader = cache.begin_read()?;
+        let table = cache_reader.open_table(self.raw_table_definition())?;
+
+        let mut n_valid = 0;
+        let mut corrupt_keys = vec![];
+        for cache_entry in table.iter()? {
+            let (key, value) = cache_entry?;
+            match serde_json::from_slice::<T>(value.value()) {
+                Ok(_) => n_valid += 1,
+                Err(_) => corrupt_keys.push(key.value().to_string()),
+            }
         }
 
-        None
+        if corrupt_keys.is_empty() {
+            Ok(n_valid)
+        } else {
+            Err(CacherError::CorruptEntries {
+                path: self.cache_file_path.clone(),
+                keys: corrupt_keys,
+            })
+        }
     }
 
     pub(crate) fn cache_object(
@@ -141,9 +533,9 @@ impl<T: Cacheable> RedbCacher<T> {
     ) -> Result<(), CacherError> {
         let cache_writer = cache.begin_write()?;
         {
-            let mut table = cache_writer.open_table(Self::table_definition())?;
+            let mut table = cache_writer.open_table(self.table_definition())?;
             for key in object_to_cache.keys() {
-                table.insert(key, object_to_cache.clone())?;
+                table.insert(key.as_str(), object_to_cache.clone())?;
             }
         }
         cache_writer.commit()?;
@@ -151,11 +543,45 @@ impl<T: Cacheable> RedbCacher<T> {
     }
 }
 
+impl<T: Cacheable> Drop for RedbCacher<T> {
+    /// Closes the cache database if no other cacher is using it. This happens while OPEN_DATABASES is locked,
+    /// so that the database can't be reopened before it is closed.
+    fn drop(&mut self) {
+        let Some(database) = self
+            .database
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        else {
+            return;
+        };
+        let mut open_databases = OPEN_DATABASES
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        drop(database);
+        // a database whose only remaining reference is the one in OPEN_DATABASES is no longer used
+        open_databases.retain(|_, database| Arc::strong_count(database) > 1);
+    }
+}
+
+impl<T: Cacheable> Cacher<T> for RedbCacher<T> {
+    fn get(&self, query: &str) -> Result<Option<T>, CacherError> {
+        let cache = self.open_cache()?;
+        Ok(self.find_cache_entry(query, &cache))
+    }
+
+    fn insert(&self, object_to_cache: T) -> Result<(), CacherError> {
+        let cache = self.open_cache()?;
+        self.cache_object(object_to_cache, &cache)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rstest::{fixture, rstest};
     use serde::{Deserialize, Serialize};
+    use std::io::ErrorKind;
     use tempfile::TempDir;
 
     #[fixture]
@@ -174,8 +600,10 @@ mod tests {
     implement_value_for_local_type!(MyFavouriteStruct);
 
     impl Cacheable for MyFavouriteStruct {
-        fn keys(&self) -> Vec<&str> {
-            vec![self.name.as_str()]
+        const TABLE_NAME: &'static str = "my_favourite_struct";
+
+        fn keys(&self) -> Vec<String> {
+            vec![self.name.clone()]
         }
     }
 
@@ -250,4 +678,365 @@ mod tests {
         let cached_alice = cacher.find_cache_entry("alice mchale", &cache).unwrap();
         assert!(cached_alice.likes_cats);
     }
+
+    #[rstest]
+    fn test_find_cache_entry_undeserializable(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.my_favourite_struct");
+        let cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path);
+        cacher.init_cache().unwrap();
+        let cache = cacher.open_cache().unwrap();
+
+        let cache_writer = cache.begin_write().unwrap();
+        {
+            let mut table = cache_writer
+                .open_table(cacher.raw_table_definition())
+                .unwrap();
+            table
+                .insert("alice mchale", b"not json".as_slice())
+                .unwrap();
+        }
+        cache_writer.commit().unwrap();
+
+        assert!(cacher.find_cache_entry("alice mchale", &cache).is_none());
+
+        cacher
+            .cache_object(my_favourite_struct_alice(), &cache)
+            .unwrap();
+        assert!(cacher.find_cache_entry("alice mchale", &cache).is_some());
+    }
+
+    #[rstest]
+    fn test_verify(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.my_favourite_struct");
+        let cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path);
+        cacher.init_cache().unwrap();
+        let cache = cacher.open_cache().unwrap();
+        cacher
+            .cache_object(my_favourite_struct_alice(), &cache)
+            .unwrap();
+        assert_eq!(cacher.verify().unwrap(), 1);
+
+        let cache_writer = cache.begin_write().unwrap();
+        {
+            let mut table = cache_writer
+                .open_table(cacher.raw_table_definition())
+                .unwrap();
+            table.insert("bob jones", b"not json".as_slice()).unwrap();
+        }
+        cache_writer.commit().unwrap();
+
+        match cacher.verify() {
+            Err(CacherError::CorruptEntries { keys, .. }) => assert_eq!(keys, vec!["bob jones"]),
+            other => panic!("Expected CorruptEntries, got {other:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_migrate_old_schema(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.my_favourite_struct");
+        let cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path);
+        cacher.init_cache().unwrap();
+        let cache = cacher.open_cache().unwrap();
+        cacher
+            .cache_object(my_favourite_struct_alice(), &cache)
+            .unwrap();
+
+        // an entry cached by an older version of MyFavouriteStruct, which had no schema version
+        let cache_writer = cache.begin_write().unwrap();
+        {
+            let mut table = cache_writer
+                .open_table(cacher.raw_table_definition())
+                .unwrap();
+            table
+                .insert("bob jones", br#"{"name": "bob jones"}"#.as_slice())
+                .unwrap();
+            let mut version_table = cache_writer.open_table(SCHEMA_VERSION_TABLE).unwrap();
+            version_table.remove("my_favourite_struct").unwrap();
+        }
+        cache_writer.commit().unwrap();
+
+        cacher.migrate(&cache).unwrap();
+
+        let cache_reader = cache.begin_read().unwrap();
+        let table = cache_reader
+            .open_table(cacher.raw_table_definition())
+            .unwrap();
+        assert!(table.get("bob jones").unwrap().is_none());
+        assert!(table.get("alice mchale").unwrap().is_some());
+        let version_table = cache_reader.open_table(SCHEMA_VERSION_TABLE).unwrap();
+        assert_eq!(
+            version_table
+                .get("my_favourite_struct")
+                .unwrap()
+                .unwrap()
+                .value(),
+            MyFavouriteStruct::SCHEMA_VERSION
+        );
+    }
+
+    #[rstest]
+    fn test_migrate_changed_keys(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.my_favourite_struct");
+        let cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path);
+        cacher.init_cache().unwrap();
+        let cache = cacher.open_cache().unwrap();
+
+        // an entry cached under a key which MyFavouriteStruct::keys no longer gives
+        let cache_writer = cache.begin_write().unwrap();
+        {
+            let mut table = cache_writer.open_table(cacher.table_definition()).unwrap();
+            table.insert("alice", my_favourite_struct_alice()).unwrap();
+            let mut version_table = cache_writer.open_table(SCHEMA_VERSION_TABLE).unwrap();
+            version_table.remove("my_favourite_struct").unwrap();
+        }
+        cache_writer.commit().unwrap();
+
+        cacher.migrate(&cache).unwrap();
+
+        assert!(cacher.find_cache_entry("alice", &cache).is_none());
+        assert!(cacher.find_cache_entry("alice mchale", &cache).is_some());
+    }
+
+    #[rstest]
+    fn test_entries(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.my_favourite_struct");
+        let cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path);
+        cacher.init_cache().unwrap();
+        let cache = cacher.open_cache().unwrap();
+
+        cacher
+            .cache_object(my_favourite_struct_bob(), &cache)
+            .unwrap();
+        cacher
+            .cache_object(my_favourite_struct_alice(), &cache)
+            .unwrap();
+
+        let entries = cacher.entries(&cache).unwrap();
+        let keys = entries
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["alice mchale", "bob jones"]);
+        assert_eq!(entries[1].1.favourite_number, 42);
+    }
+
+    #[rstest]
+    fn test_try_default_with_override(temp_dir: TempDir) {
+        let cache_dir = temp_dir.path().join("pivot_cache");
+
+        let cacher = RedbCacher::<MyFavouriteStruct>::try_default_with_override(Some(
+            cache_dir.clone().into_os_string(),
+        ))
+        .unwrap();
+
+        assert!(cache_dir.is_dir());
+        assert!(cacher.cache_file_path().starts_with(&cache_dir));
+    }
+
+    #[rstest]
+    fn test_try_open_nonexistent_cache(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.my_favourite_struct");
+        let cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path.clone());
+
+        assert!(cacher.try_open().unwrap().is_none());
+        assert!(!cacher.contains("alice mchale").unwrap());
+        assert!(!cache_file_path.exists());
+    }
+
+    #[rstest]
+    fn test_contains_read_only(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.my_favourite_struct");
+        let writing_cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path.clone());
+        writing_cacher.init_cache().unwrap();
+        writing_cacher.insert(my_favourite_struct_alice()).unwrap();
+        // the cache is closed once no cacher is using it, so the next cacher opens it read-only
+        drop(writing_cacher);
+
+        let cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path);
+        assert!(cacher.contains("alice mchale").unwrap());
+        assert!(!cacher.contains("bob jones").unwrap());
+    }
+
+    #[rstest]
+    fn test_contains_unreadable_cache_err(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.my_favourite_struct");
+        fs::write(&cache_file_path, "not a database").unwrap();
+        let cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path);
+
+        assert!(matches!(
+            cacher.contains("alice mchale"),
+            Err(CacherError::Open { .. })
+        ));
+        assert_eq!(
+            fs::read_to_string(cacher.cache_file_path()).unwrap(),
+            "not a database"
+        );
+    }
+
+    #[rstest]
+    fn test_contains(temp_dir: TempDir) {
+        let cacher =
+            RedbCacher::<MyFavouriteStruct>::new(temp_dir.path().join("cache.my_favourite_struct"));
+        cacher.init_cache().unwrap();
+        cacher.insert(my_favourite_struct_alice()).unwrap();
+
+        assert!(cacher.contains("alice mchale").unwrap());
+        assert!(!cacher.contains("bob jones").unwrap());
+    }
+
+    #[rstest]
+    fn test_or_temp_dir(temp_dir: TempDir) {
+        let file_path = temp_dir.path().join("not_a_directory");
+        fs::write(&file_path, "").unwrap();
+
+        let cacher = RedbCacher::<MyFavouriteStruct>::or_temp_dir(RedbCacher::in_dir(
+            file_path.join("cache"),
+        ));
+
+        assert!(cacher.cache_file_path().starts_with(env::temp_dir()));
+        assert!(cacher.open_cache().is_ok());
+    }
+
+    #[rstest]
+    fn test_default_caches_share_dir(temp_dir: TempDir) {
+        let cache_dir = temp_dir.path().join("pivot_cache");
+
+        let cachers = (0..3)
+            .map(|_| {
+                RedbCacher::<MyFavouriteStruct>::or_temp_dir(RedbCacher::try_default_with_override(
+                    Some(cache_dir.clone().into_os_string()),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        for cacher in &cachers {
+            assert!(cacher.cache_file_path().starts_with(&cache_dir));
+        }
+        cachers[0].insert(my_favourite_struct_alice()).unwrap();
+        assert!(cachers[2].get("alice mchale").unwrap().is_some());
+    }
+
+    #[rstest]
+    fn test_cachers_share_open_database(temp_dir: TempDir) {
+        fs::create_dir(temp_dir.path().join("subdir")).unwrap();
+        let cache_file_path = temp_dir.path().join("cache.my_favourite_struct");
+        let cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path.clone());
+        let other_cacher = RedbCacher::<MyFavouriteStruct>::new(
+            temp_dir.path().join("subdir/../cache.my_favourite_struct"),
+        );
+
+        cacher.init_cache().unwrap();
+        other_cacher.init_cache().unwrap();
+        cacher.insert(my_favourite_struct_alice()).unwrap();
+
+        assert!(other_cacher.get("alice mchale").unwrap().is_some());
+        drop(cacher);
+        drop(other_cacher);
+        assert!(
+            !OPEN_DATABASES
+                .lock()
+                .unwrap()
+                .contains_key(&canonical_cache_path(&cache_file_path))
+        );
+    }
+
+    #[rstest]
+    fn test_in_dir_uncreatable(temp_dir: TempDir) {
+        let file_path = temp_dir.path().join("not_a_directory");
+        fs::write(&file_path, "").unwrap();
+
+        let result = RedbCacher::<MyFavouriteStruct>::in_dir(file_path.join("cache"));
+
+        assert!(
+            matches!(result, Err(CacherError::Io(err)) if err.kind() == ErrorKind::NotADirectory)
+        );
+    }
+
+    #[rstest]
+    fn test_cache_with_table_name(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.my_favourite_struct");
+        let cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path.clone());
+        cacher.init_cache().unwrap();
+        let cache = cacher.open_cache().unwrap();
+        cacher
+            .cache_object(my_favourite_struct_alice(), &cache)
+            .unwrap();
+
+        let same_table_cacher = RedbCacher::<MyFavouriteStruct>::new_with_table_name(
+            cache_file_path.clone(),
+            "my_favourite_struct",
+        );
+        let cache = same_table_cacher.open_cache().unwrap();
+        assert!(
+            same_table_cacher
+                .find_cache_entry("alice mchale", &cache)
+                .is_some()
+        );
+
+        let other_table_cacher = RedbCacher::<MyFavouriteStruct>::new_with_table_name(
+            cache_file_path,
+            "crate::moved::MyFavouriteStruct",
+        );
+        other_table_cacher.init_cache().unwrap();
+        let cache = other_table_cacher.open_cache().unwrap();
+        assert!(
+            other_table_cacher
+                .find_cache_entry("alice mchale", &cache)
+                .is_none()
+        );
+    }
+
+    #[rstest]
+    fn test_init_cache_moves_legacy_table(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.my_favourite_struct");
+        let legacy_cacher = RedbCacher::<MyFavouriteStruct>::new_with_table_name(
+            cache_file_path.clone(),
+            type_name::<MyFavouriteStruct>(),
+        );
+        legacy_cacher.init_cache().unwrap();
+        legacy_cacher.insert(my_favourite_struct_alice()).unwrap();
+
+        let cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path);
+        cacher.init_cache().unwrap();
+
+        assert!(cacher.get("alice mchale").unwrap().is_some());
+        let cache = cacher.open_cache().unwrap();
+        let cache_reader = cache.begin_read().unwrap();
+        assert!(
+            !cache_reader
+                .list_tables()
+                .unwrap()
+                .any(|table| table.name() == type_name::<MyFavouriteStruct>())
+        );
+    }
+
+    #[rstest]
+    fn test_in_dir_moves_legacy_cache_file(temp_dir: TempDir) {
+        let legacy_cache_file_path = temp_dir.path().join(type_name::<MyFavouriteStruct>());
+        fs::write(&legacy_cache_file_path, "legacy cache").unwrap();
+
+        let cacher =
+            RedbCacher::<MyFavouriteStruct>::in_dir(temp_dir.path().to_path_buf()).unwrap();
+
+        assert_eq!(
+            cacher.cache_file_path(),
+            &temp_dir.path().join("my_favourite_struct")
+        );
+        assert!(cacher.cache_file_path().exists());
+        assert!(!legacy_cache_file_path.exists());
+    }
+
+    #[rstest]
+    fn test_init_cache_error_contains_path(temp_dir: TempDir) {
+        let cache_file_path = temp_dir
+            .path()
+            .join("missing_directory")
+            .join("cache.my_favourite_struct");
+        let cacher = RedbCacher::<MyFavouriteStruct>::new(cache_file_path.clone());
+
+        let err = cacher.init_cache().unwrap_err();
+
+        assert!(matches!(err, CacherError::Open { .. }));
+        assert!(err.to_string().contains(cache_file_path.to_str().unwrap()));
+    }
 }
@@ -1,14 +1,34 @@
+use crate::caching::error::CacherError;
 use redb::Value;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::borrow::Borrow;
+use std::fmt::Debug;
 
 /// If an object implements Cacheable then a RedbCacher can be constructed for it.
 ///
 /// - for<'a> Self: From<Self::SelfType<'a>> is required so that cache_entry.value().into() works
 /// - for<'a> Self: Borrow<Self::SelfType<'a>> is required so that table.insert(key, object_to_cache.clone())?; works
-pub trait Cacheable: Sized + Clone + Value + 'static
+/// - Serialize + DeserializeOwned is required so that cache entries can be deserialized without panicking
+pub trait Cacheable: Sized + Clone + Value + Serialize + DeserializeOwned + 'static
 where
     for<'a> Self: From<Self::SelfType<'a>>,
     for<'a> Self: Borrow<Self::SelfType<'a>>,
 {
-    fn keys(&self) -> Vec<&str>;
+    /// The default name of the table in which the objects are cached.
+    /// This should not be changed, as caches using the previous name would no longer be found.
+    const TABLE_NAME: &'static str;
+
+    /// The version of the serialized form of the objects. This should be incremented whenever a change to the object
+    /// would stop cached objects from being deserialized, or a change to keys would stop them from being found,
+    /// so that the cache entries are migrated.
+    const SCHEMA_VERSION: u32 = 1;
+
+    fn keys(&self) -> Vec<String>;
+}
+
+/// A store in which Cacheable objects can be cached and later found again by any of their keys.
+pub trait Cacher<T: Cacheable>: Debug {
+    fn get(&self, query: &str) -> Result<Option<T>, CacherError>;
+    fn insert(&self, object_to_cache: T) -> Result<(), CacherError>;
 }
@@ -7,7 +7,6 @@ use crate::hgnc::traits::HGNCData;
 use std::fmt::{Debug, Formatter};
 use std::path::PathBuf;
 
-#[derive(Default)]
 pub struct CachedHGNCClient {
     cacher: RedbCacher<GeneDoc>,
     hgnc_client: HGNCClient,
@@ -35,6 +34,44 @@ impl CachedHGNCClient {
             hgnc_client,
         })
     }
+
+    /// Constructs a CachedHGNCClient whose cache is in the cache directory of the OS, or in PIVOT_CACHE_DIR if that environment variable is set.
+    pub fn try_default() -> Result<Self, HGNCError> {
+        let cacher = RedbCacher::try_default()?;
+        cacher.init_cache()?;
+        Ok(CachedHGNCClient {
+            cacher,
+            hgnc_client: HGNCClient::default(),
+        })
+    }
+
+    /// Requests and caches the GeneDoc of every query (e.g. before a run over a gene panel), so that later requests are cache hits.
+    ///
+    /// Genes which are already cached are not requested again. The results are in the same order as the queries.
+    pub fn warm(&self, queries: Vec<GeneQuery>) -> Vec<Result<GeneDoc, HGNCError>> {
+        queries
+            .into_iter()
+            .map(|query| self.request_gene_data(query))
+            .collect()
+    }
+
+    /// Checks that every cached GeneDoc can be read, returning the number of cache entries (an error lists any unreadable entries).
+    ///
+    /// Each GeneDoc has an entry for its symbol and one for its HGNC ID.
+    pub fn verify_cache(&self) -> Result<usize, HGNCError> {
+        Ok(self.cacher.verify()?)
+    }
+}
+
+impl Default for CachedHGNCClient {
+    /// If the default cache can't be created, a cache in the temp directory is used instead.
+    /// Use `CachedHGNCClient::try_default` to handle this case as an error.
+    fn default() -> Self {
+        CachedHGNCClient {
+            cacher: RedbCacher::default_or_temp(),
+            hgnc_client: HGNCClient::default(),
+        }
+    }
 }
 
 impl Debug for CachedHGNCClient {
@@ -53,6 +90,16 @@ mod tests {
     use rstest::{fixture, rstest};
     use tempfile::TempDir;
 
+    const ZNF3_RESPONSE: &str = r#"{
+        "responseHeader": {"status": 0, "QTime": 1},
+        "response": {
+            "numFound": 1,
+            "start": 0,
+            "numFoundExact": true,
+            "docs": [{"symbol": "ZNF3", "hgnc_id": "HGNC:13089"}]
+        }
+    }"#;
+
     #[fixture]
     fn temp_dir() -> TempDir {
         tempfile::tempdir().expect("Failed to create temporary directory")
@@ -72,6 +119,64 @@ mod tests {
         assert_eq!(cached_gene_doc.hgnc_id, Some("HGNC:2082".to_string()));
     }
 
+    #[rstest]
+    fn test_cache_by_symbol_then_request_by_id(temp_dir: TempDir) {
+        let mut server = mockito::Server::new();
+        let hgnc = server
+            .mock("GET", "/fetch/symbol/ZNF3")
+            .with_body(ZNF3_RESPONSE)
+            .expect(1)
+            .create();
+        let hgnc_client = HGNCClient::default().with_api_url(format!("{}/", server.url()));
+        let client =
+            CachedHGNCClient::new(temp_dir.path().join("cache.hgnc"), hgnc_client).unwrap();
+
+        client.request_gene_data(GeneQuery::Symbol("ZNF3")).unwrap();
+        let gene_doc = client
+            .request_gene_data(GeneQuery::HgncId("HGNC:13089"))
+            .unwrap();
+
+        hgnc.assert();
+        assert_eq!(gene_doc.symbol, Some("ZNF3".to_string()));
+    }
+
+    #[rstest]
+    fn test_warm(temp_dir: TempDir) {
+        let mut server = mockito::Server::new();
+        let znf3 = server
+            .mock("GET", "/fetch/symbol/ZNF3")
+            .with_body(ZNF3_RESPONSE)
+            .expect(1)
+            .create();
+        let not_a_gene = server
+            .mock("GET", "/fetch/symbol/NOTAGENE")
+            .with_body(
+                ZNF3_RESPONSE.replace(r#"[{"symbol": "ZNF3", "hgnc_id": "HGNC:13089"}]"#, "[]"),
+            )
+            .expect(1)
+            .create();
+        let hgnc_client = HGNCClient::default().with_api_url(format!("{}/", server.url()));
+        let client =
+            CachedHGNCClient::new(temp_dir.path().join("cache.hgnc"), hgnc_client).unwrap();
+
+        let results = client.warm(vec![
+            GeneQuery::Symbol("ZNF3"),
+            GeneQuery::Symbol("NOTAGENE"),
+            GeneQuery::HgncId("HGNC:13089"),
+        ]);
+        let gene_doc = client.request_gene_data(GeneQuery::Symbol("ZNF3")).unwrap();
+
+        znf3.assert();
+        not_a_gene.assert();
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(HGNCError::UnexpectedNumberOfDocuments { n_found: 0, .. })
+        ));
+        assert!(results[2].is_ok());
+        assert_eq!(gene_doc.hgnc_id(), Some("HGNC:13089"));
+    }
+
     #[rstest]
     #[case(GeneQuery::Symbol("ZNF3"), ("ZNF3", "HGNC:13089"))]
     #[case(GeneQuery::HgncId("HGNC:13089"), ("ZNF3", "HGNC:13089"))]
@@ -1,6 +1,8 @@
+use crate::hgnc::error::HGNCError;
 use crate::utils::is_hgnc_id;
+use std::fmt::Display;
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum GeneQuery<'a> {
     Symbol(&'a str),
     HgncId(&'a str),
@@ -23,4 +25,52 @@ impl<'a> GeneQuery<'a> {
             GeneQuery::HgncId(s) => s,
         }
     }
+
+    /// Parses the form given by Display, e.g., symbol:KIF21A or hgnc:HGNC:19349.
+    ///
+    /// This is not FromStr, as the GeneQuery borrows from the string.
+    pub fn parse(query: &'a str) -> Result<Self, HGNCError> {
+        match query.split_once(':') {
+            Some(("symbol", symbol)) if !symbol.is_empty() => Ok(GeneQuery::Symbol(symbol)),
+            Some(("hgnc", hgnc_id)) if !hgnc_id.is_empty() => Ok(GeneQuery::HgncId(hgnc_id)),
+            _ => Err(HGNCError::InvalidGeneQuery {
+                query: query.to_string(),
+            }),
+        }
+    }
+}
+
+impl Display for GeneQuery<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeneQuery::Symbol(symbol) => write!(f, "symbol:{symbol}"),
+            GeneQuery::HgncId(hgnc_id) => write!(f, "hgnc:{hgnc_id}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(GeneQuery::Symbol("KIF21A"), "symbol:KIF21A")]
+    #[case(GeneQuery::HgncId("HGNC:19349"), "hgnc:HGNC:19349")]
+    fn test_gene_query_round_trip(#[case] gene_query: GeneQuery, #[case] expected: &str) {
+        let gene_query_string = gene_query.to_string();
+        assert_eq!(gene_query_string, expected);
+        assert_eq!(GeneQuery::parse(&gene_query_string).unwrap(), gene_query);
+    }
+
+    #[rstest]
+    #[case("KIF21A")]
+    #[case("symbol:")]
+    #[case("gene:KIF21A")]
+    fn test_gene_query_parse_err(#[case] query: &str) {
+        assert!(matches!(
+            GeneQuery::parse(query),
+            Err(HGNCError::InvalidGeneQuery { .. })
+        ));
+    }
 }
@@ -12,6 +12,20 @@ pub enum HGNCError {
         n_found: usize,
         n_expected: usize,
     },
+    #[error("HGNC request for '{identifier}' did not succeed after {attempts} attempts.")]
+    Api { identifier: String, attempts: usize },
+    #[error("HGNC request for '{identifier}' timed out.")]
+    Timeout { identifier: String },
+    #[error("Could not connect to HGNC for '{identifier}': {problem}")]
+    ConnectionFailed { identifier: String, problem: String },
+    #[error("HGNC request for '{identifier}' failed with HTTP status {code}.")]
+    HttpStatus { identifier: String, code: u16 },
+    #[error("HGNC could not be reached at {url}: {problem}")]
+    Unreachable { url: String, problem: String },
+    #[error(
+        "Could not parse gene query '{query}'. Expected the form symbol:<gene symbol> or hgnc:<HGNC ID>."
+    )]
+    InvalidGeneQuery { query: String },
     #[error("No {desired_element} found in GeneDoc.")]
     MissingElementInDocument { desired_element: String },
     #[error("Cant establish caching dir {0}")]
@@ -2,41 +2,220 @@ use crate::hgnc::enums::GeneQuery;
 use crate::hgnc::error::HGNCError;
 use crate::hgnc::json_schema::{GeneDoc, GeneResponse};
 use crate::hgnc::traits::HGNCData;
+use log::warn;
 use ratelimit::Ratelimiter;
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, RequestBuilder};
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::thread::sleep;
 use std::time::Duration;
 
+/// How many symbols are combined into one search query, so that its URL stays well within length limits.
+const SYMBOLS_PER_SEARCH: usize = 100;
+
 pub struct HGNCClient {
     rate_limiter: Ratelimiter,
+    attempts: usize,
+    retry_backoff: Duration,
     api_url: String,
     client: Client,
+    extra_headers: HashMap<String, String>,
 }
 
 impl HGNCClient {
     pub fn new(rate_limiter: Ratelimiter, api_url: String) -> Self {
+        HGNCClient::with_client(rate_limiter, api_url, Client::new())
+    }
+
+    fn with_client(rate_limiter: Ratelimiter, api_url: String, client: Client) -> Self {
         HGNCClient {
             rate_limiter,
+            attempts: 3,
+            retry_backoff: Duration::from_millis(500),
             api_url,
-            client: Client::new(),
+            client,
+            extra_headers: HashMap::new(),
         }
     }
 
-    fn fetch_request(&self, url: String) -> Result<Vec<GeneDoc>, HGNCError> {
-        if let Err(duration) = self.rate_limiter.try_wait() {
-            sleep(duration);
+    /// Sets headers (e.g. an API key) which are sent with every request, in addition to the default User-Agent and Accept headers.
+    pub fn with_extra_headers(mut self, extra_headers: HashMap<String, String>) -> Self {
+        self.extra_headers = extra_headers;
+        self
+    }
+
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into();
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, rate_limiter: Ratelimiter) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Sets how many times a request is attempted before giving up.
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Sets how long to wait after a failed attempt. The wait grows linearly with each further failed attempt.
+    pub fn with_retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    /// Creates a client with the default settings, which will make its requests using the given reqwest Client.
+    ///
+    /// A reqwest Client holds a connection pool, so sharing a single Client between all HGNC and HGVS clients is recommended.
+    pub fn with_shared_client(client: Client) -> Self {
+        let rate_limiter = Ratelimiter::builder(10, Duration::from_secs(1))
+            .max_tokens(10)
+            .build()
+            .expect("Building rate limiter failed");
+
+        HGNCClient::with_client(
+            rate_limiter,
+            "https://rest.genenames.org/".to_string(),
+            client,
+        )
+    }
+
+    /// Resolves many gene symbols to their HGNC IDs, using one search request per chunk of symbols rather than a request per symbol.
+    ///
+    /// The returned map is keyed by the symbols as they were given, since HGNC matches them case-insensitively.
+    /// Symbols which HGNC does not know are absent from the returned map.
+    pub fn resolve_symbols(&self, symbols: &[&str]) -> Result<HashMap<String, String>, HGNCError> {
+        let mut symbol_to_id = HashMap::new();
+        for chunk in symbols.chunks(SYMBOLS_PER_SEARCH) {
+            let query = chunk
+                .iter()
+                .map(|symbol| format!("symbol:{}", Self::encode_path_segment(symbol)))
+                .collect::<Vec<String>>()
+                .join("+OR+");
+            let search_url = format!("{}search/{}", self.api_url, query);
+            let docs = self.fetch_request(search_url, &chunk.join(", "))?;
+
+            for doc in docs {
+                if let (Some(symbol), Some(hgnc_id)) = (doc.symbol, doc.hgnc_id) {
+                    for requested in chunk
+                        .iter()
+                        .filter(|requested| requested.eq_ignore_ascii_case(&symbol))
+                    {
+                        symbol_to_id.insert(requested.to_string(), hgnc_id.clone());
+                    }
+                }
+            }
         }
+        Ok(symbol_to_id)
+    }
+
+    /// Percent-encodes every byte of a path segment except the unreserved characters of RFC 3986.
+    fn encode_path_segment(segment: &str) -> String {
+        segment
+            .bytes()
+            .map(|byte| match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    (byte as char).to_string()
+                }
+                _ => format!("%{byte:02X}"),
+            })
+            .collect()
+    }
+
+    /// Checks that HGNC is reachable and accepts the client's requests (e.g. its extra headers), using the info endpoint.
+    pub fn ping(&self) -> Result<(), HGNCError> {
+        let ping_url = format!("{}info", self.api_url);
+        let unreachable = |problem: String| HGNCError::Unreachable {
+            url: ping_url.clone(),
+            problem,
+        };
+
         let response = self
+            .get(&ping_url)
+            .send()
+            .map_err(|err| unreachable(err.to_string()))?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(unreachable(format!("status {}", response.status())))
+        }
+    }
+
+    fn get(&self, url: &str) -> RequestBuilder {
+        let mut request = self
             .client
-            .get(url.clone())
+            .get(url)
             .header("User-Agent", "PIVOT")
-            .header("Accept", "application/json")
-            .send()?;
+            .header("Accept", "application/json");
+        for (name, value) in &self.extra_headers {
+            request = request.header(name, value);
+        }
+        request
+    }
+
+    fn fetch_request(&self, url: String, identifier: &str) -> Result<Vec<GeneDoc>, HGNCError> {
+        for attempt in 1..=self.attempts {
+            if let Err(duration) = self.rate_limiter.try_wait() {
+                sleep(duration);
+            }
+            let response = match self.get(&url).send() {
+                Ok(response) => response,
+                // a timeout or dropped connection may be resolved by retrying
+                Err(err) if attempt < self.attempts => {
+                    warn!("HGNC request for '{identifier}' failed on attempt {attempt}: {err}");
+                    sleep(self.retry_backoff * attempt as u32);
+                    continue;
+                }
+                Err(err) => return Err(Self::request_error(identifier, err)),
+            };
 
-        let gene_response = response.json::<GeneResponse>()?;
+            let status = response.status();
+            if status.is_success() {
+                let gene_response = response
+                    .json::<GeneResponse>()
+                    .map_err(|err| Self::request_error(identifier, err))?;
+                return Ok(gene_response.response.docs);
+            }
+            // a client error will not be resolved by retrying
+            if status.is_client_error() {
+                return Err(HGNCError::HttpStatus {
+                    identifier: identifier.to_string(),
+                    code: status.as_u16(),
+                });
+            }
+
+            if attempt < self.attempts {
+                sleep(self.retry_backoff * attempt as u32);
+            }
+        }
 
-        Ok(gene_response.response.docs)
+        Err(HGNCError::Api {
+            identifier: identifier.to_string(),
+            attempts: self.attempts,
+        })
+    }
+
+    /// Distinguishes timeouts, connection failures and error statuses, so that callers can react to them differently.
+    fn request_error(identifier: &str, err: reqwest::Error) -> HGNCError {
+        if err.is_timeout() {
+            HGNCError::Timeout {
+                identifier: identifier.to_string(),
+            }
+        } else if err.is_connect() {
+            HGNCError::ConnectionFailed {
+                identifier: identifier.to_string(),
+                problem: err.to_string(),
+            }
+        } else if let Some(status) = err.status() {
+            HGNCError::HttpStatus {
+                identifier: identifier.to_string(),
+                code: status.as_u16(),
+            }
+        } else {
+            HGNCError::Request(err)
+        }
     }
 }
 
@@ -46,7 +225,7 @@ impl HGNCData for HGNCClient {
             GeneQuery::Symbol(symbol) => format!("{}fetch/symbol/{}", self.api_url, symbol),
             GeneQuery::HgncId(id) => format!("{}fetch/hgnc_id/{}", self.api_url, id),
         };
-        let docs = self.fetch_request(fetch_url)?;
+        let docs = self.fetch_request(fetch_url, query.inner())?;
 
         if docs.len() == 1 {
             Ok(docs.first().unwrap().clone())
@@ -62,12 +241,7 @@ impl HGNCData for HGNCClient {
 
 impl Default for HGNCClient {
     fn default() -> Self {
-        let rate_limiter = Ratelimiter::builder(10, Duration::from_secs(1))
-            .max_tokens(10)
-            .build()
-            .expect("Building rate limiter failed");
-
-        HGNCClient::new(rate_limiter, "https://rest.genenames.org/".to_string())
+        HGNCClient::with_shared_client(Client::new())
     }
 }
 
@@ -75,6 +249,9 @@ impl Debug for HGNCClient {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("HGNCClient")
             .field("api_url", &self.api_url)
+            .field("attempts", &self.attempts)
+            .field("retry_backoff", &self.retry_backoff)
+            .field("extra_headers", &self.extra_headers.keys()) // values may be secret
             .field("rate_limiter", &"<Ratelimiter>")
             .finish()
     }
@@ -84,6 +261,272 @@ impl Debug for HGNCClient {
 mod tests {
     use super::*;
     use rstest::rstest;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    const ZNF3_RESPONSE: &str = r#"{
+        "responseHeader": {"status": 0, "QTime": 1},
+        "response": {
+            "numFound": 1,
+            "start": 0,
+            "numFoundExact": true,
+            "docs": [{"symbol": "ZNF3", "hgnc_id": "HGNC:13089"}]
+        }
+    }"#;
+
+    fn mock_server_client(server: &mockito::Server, attempts: usize) -> HGNCClient {
+        let rate_limiter = Ratelimiter::builder(10, Duration::from_secs(1))
+            .max_tokens(10)
+            .build()
+            .unwrap();
+        HGNCClient::new(rate_limiter, format!("{}/", server.url()))
+            .with_attempts(attempts)
+            .with_retry_backoff(Duration::ZERO)
+    }
+
+    #[rstest]
+    fn test_request_gene_data_retries_on_server_error() {
+        let mut server = mockito::Server::new();
+        let unavailable = server
+            .mock("GET", "/fetch/symbol/ZNF3")
+            .with_status(503)
+            .expect(2)
+            .create();
+        let available = server
+            .mock("GET", "/fetch/symbol/ZNF3")
+            .with_status(200)
+            .with_body(ZNF3_RESPONSE)
+            .expect(1)
+            .create();
+        let client = mock_server_client(&server, 3);
+
+        let gene_doc = client.request_gene_data(GeneQuery::Symbol("ZNF3")).unwrap();
+
+        unavailable.assert();
+        available.assert();
+        assert_eq!(gene_doc.hgnc_id, Some("HGNC:13089".to_string()));
+    }
+
+    #[rstest]
+    fn test_request_gene_data_retries_on_dropped_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let api_url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            // the first connection is dropped without a response
+            drop(listener.accept().unwrap());
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = BufReader::new(&stream);
+            let mut line = String::new();
+            // the request headers end with an empty line
+            while request.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                ZNF3_RESPONSE.len(),
+                ZNF3_RESPONSE
+            )
+            .unwrap();
+        });
+        let client = HGNCClient::default()
+            .with_api_url(api_url)
+            .with_retry_backoff(Duration::ZERO);
+
+        let gene_doc = client.request_gene_data(GeneQuery::Symbol("ZNF3")).unwrap();
+
+        server.join().unwrap();
+        assert_eq!(gene_doc.hgnc_id, Some("HGNC:13089".to_string()));
+    }
+
+    #[rstest]
+    fn test_extra_headers_are_sent() {
+        let mut server = mockito::Server::new();
+        let with_api_key = server
+            .mock("GET", "/fetch/symbol/ZNF3")
+            .match_header("X-API-Key", "secret-key")
+            .match_header("Accept", "application/json")
+            .with_body(ZNF3_RESPONSE)
+            .create();
+        let client = mock_server_client(&server, 1).with_extra_headers(HashMap::from([(
+            "X-API-Key".to_string(),
+            "secret-key".to_string(),
+        )]));
+
+        client.request_gene_data(GeneQuery::Symbol("ZNF3")).unwrap();
+
+        with_api_key.assert();
+    }
+
+    #[rstest]
+    fn test_resolve_symbols() {
+        let mut server = mockito::Server::new();
+        let search = server
+            .mock(
+                "GET",
+                "/search/symbol:KIF21A+OR+symbol:ZNF3+OR+symbol:NOTAGENE",
+            )
+            .with_body(
+                r#"{
+                    "responseHeader": {"status": 0, "QTime": 2},
+                    "response": {
+                        "numFound": 2,
+                        "start": 0,
+                        "numFoundExact": true,
+                        "maxScore": 1.0,
+                        "docs": [
+                            {"hgnc_id": "HGNC:19349", "symbol": "KIF21A", "score": 1.0},
+                            {"hgnc_id": "HGNC:13089", "symbol": "ZNF3", "score": 1.0}
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let symbol_to_id = mock_server_client(&server, 1)
+            .resolve_symbols(&["KIF21A", "ZNF3", "NOTAGENE"])
+            .unwrap();
+
+        search.assert();
+        assert_eq!(symbol_to_id.len(), 2);
+        assert_eq!(symbol_to_id["KIF21A"], "HGNC:19349");
+        assert_eq!(symbol_to_id["ZNF3"], "HGNC:13089");
+    }
+
+    #[rstest]
+    fn test_resolve_symbols_case_insensitive() {
+        let mut server = mockito::Server::new();
+        let search = server
+            .mock("GET", "/search/symbol:znf3+OR+symbol:NOT%20A%2FGENE")
+            .with_body(ZNF3_RESPONSE)
+            .expect(1)
+            .create();
+
+        let symbol_to_id = mock_server_client(&server, 1)
+            .resolve_symbols(&["znf3", "NOT A/GENE"])
+            .unwrap();
+
+        search.assert();
+        assert_eq!(
+            symbol_to_id,
+            HashMap::from([("znf3".to_string(), "HGNC:13089".to_string())])
+        );
+    }
+
+    #[rstest]
+    fn test_resolve_symbols_chunks() {
+        let mut server = mockito::Server::new();
+        let search = server
+            .mock("GET", mockito::Matcher::Regex("^/search/".to_string()))
+            .with_body(ZNF3_RESPONSE)
+            .expect(2)
+            .create();
+        let symbols = vec!["ZNF3"; SYMBOLS_PER_SEARCH + 1];
+
+        let symbol_to_id = mock_server_client(&server, 1)
+            .resolve_symbols(&symbols)
+            .unwrap();
+
+        search.assert();
+        assert_eq!(symbol_to_id.len(), 1);
+    }
+
+    #[rstest]
+    fn test_ping() {
+        let mut server = mockito::Server::new();
+        let info = server.mock("GET", "/info").with_status(200).create();
+
+        mock_server_client(&server, 1).ping().unwrap();
+
+        info.assert();
+    }
+
+    #[rstest]
+    fn test_ping_err() {
+        let mut server = mockito::Server::new();
+        server.mock("GET", "/info").with_status(401).create();
+
+        let result = mock_server_client(&server, 1).ping();
+
+        assert!(matches!(result, Err(HGNCError::Unreachable { .. })));
+    }
+
+    #[rstest]
+    fn test_request_gene_data_attempts_exhausted() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/fetch/symbol/ZNF3")
+            .with_status(503)
+            .expect(2)
+            .create();
+        let client = mock_server_client(&server, 2);
+
+        let result = client.request_gene_data(GeneQuery::Symbol("ZNF3"));
+
+        assert!(matches!(
+            result,
+            Err(HGNCError::Api { identifier, attempts: 2 }) if identifier == "ZNF3"
+        ));
+    }
+
+    #[rstest]
+    fn test_request_gene_data_not_found() {
+        let mut server = mockito::Server::new();
+        let not_found = server
+            .mock("GET", "/fetch/symbol/ZNF3")
+            .with_status(404)
+            .expect(1)
+            .create();
+        let client = mock_server_client(&server, 3);
+
+        let result = client.request_gene_data(GeneQuery::Symbol("ZNF3"));
+
+        not_found.assert();
+        assert!(matches!(
+            result,
+            Err(HGNCError::HttpStatus { identifier, code: 404 }) if identifier == "ZNF3"
+        ));
+    }
+
+    #[rstest]
+    fn test_request_gene_data_timeout() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/fetch/symbol/ZNF3")
+            .with_chunked_body(|body| {
+                sleep(Duration::from_millis(500));
+                body.write_all(ZNF3_RESPONSE.as_bytes())
+            })
+            .create();
+        let client = HGNCClient::with_shared_client(
+            Client::builder()
+                .timeout(Duration::from_millis(100))
+                .build()
+                .unwrap(),
+        )
+        .with_api_url(format!("{}/", server.url()))
+        .with_attempts(1);
+
+        let result = client.request_gene_data(GeneQuery::Symbol("ZNF3"));
+
+        assert!(matches!(
+            result,
+            Err(HGNCError::Timeout { identifier }) if identifier == "ZNF3"
+        ));
+    }
+
+    #[rstest]
+    fn test_request_gene_data_connection_failed() {
+        let client = HGNCClient::default()
+            .with_api_url("http://127.0.0.1:1/")
+            .with_retry_backoff(Duration::ZERO);
+
+        let result = client.request_gene_data(GeneQuery::Symbol("ZNF3"));
+
+        assert!(matches!(result, Err(HGNCError::ConnectionFailed { .. })));
+    }
 
     #[rstest]
     #[case(GeneQuery::Symbol("ZNF3"), "ZNF3", "HGNC:13089")]
@@ -115,6 +115,51 @@ impl GeneDoc {
         (self.symbol_owned(), self.hgnc_id_owned())
     }
 
+    /// Whether the two docs describe the same gene, comparing only the identifiers of the gene.
+    ///
+    /// Unlike `==`, this ignores fields which change between HGNC releases, such as date_modified.
+    pub fn same_gene(&self, other: &Self) -> bool {
+        self.hgnc_id == other.hgnc_id
+            && self.symbol == other.symbol
+            && self.ensembl_gene_id == other.ensembl_gene_id
+            && self.entrez_id == other.entrez_id
+    }
+
+    /// The approved name of the gene, e.g., kinesin family member 21A
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The cytogenetic location of the gene, e.g., 12q12
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    /// The locus type of the gene, e.g., gene with protein product
+    pub fn locus_type(&self) -> Option<&str> {
+        self.locus_type.as_deref()
+    }
+
+    /// The locus group of the gene, e.g., protein-coding gene, pseudogene or non-coding RNA
+    pub fn locus_group(&self) -> Option<&str> {
+        self.locus_group.as_deref()
+    }
+
+    /// Whether HGNC groups the gene as a protein-coding gene, i.e. it is not a pseudogene, RNA gene etc.
+    pub fn is_protein_coding(&self) -> bool {
+        self.locus_group() == Some("protein-coding gene")
+    }
+
+    /// The accessions of the MANE Select transcript of the gene, e.g., ENST00000361418.10 and NM_001173464.2
+    pub fn mane_select_transcripts(&self) -> &[String] {
+        &self.mane_select
+    }
+
+    /// The first accession of the MANE Select transcript of the gene, if it has one.
+    pub fn mane_select_transcript(&self) -> Option<&str> {
+        self.mane_select.first().map(String::as_str)
+    }
+
     pub fn with_hgnc_id(mut self, hgnc_id: impl Into<String>) -> Self {
         self.hgnc_id = Some(hgnc_id.into());
         self
@@ -124,4 +169,119 @@ impl GeneDoc {
         self.symbol = Some(symbol.into());
         self
     }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn with_locus_type(mut self, locus_type: impl Into<String>) -> Self {
+        self.locus_type = Some(locus_type.into());
+        self
+    }
+
+    pub fn with_locus_group(mut self, locus_group: impl Into<String>) -> Self {
+        self.locus_group = Some(locus_group.into());
+        self
+    }
+
+    pub fn with_mane_select(mut self, mane_select: Vec<String>) -> Self {
+        self.mane_select = mane_select;
+        self
+    }
+
+    pub fn with_location(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    #[fixture]
+    fn kif21a() -> GeneDoc {
+        GeneDoc {
+            ensembl_gene_id: Some("ENSG00000139116".to_string()),
+            entrez_id: Some("55605".to_string()),
+            date_modified: Some("2023-01-20".to_string()),
+            ..GeneDoc::default()
+                .with_symbol("KIF21A")
+                .with_hgnc_id("HGNC:19349")
+        }
+    }
+
+    #[rstest]
+    fn test_same_gene(kif21a: GeneDoc) {
+        let updated_kif21a = GeneDoc {
+            date_modified: Some("2025-06-03".to_string()),
+            uuid: Some("5e6f1a2b-6c0d-4a8e-9f3b-2d7c1e4a9b80".to_string()),
+            ..kif21a.clone()
+        };
+
+        assert_ne!(kif21a, updated_kif21a);
+        assert!(kif21a.same_gene(&updated_kif21a));
+    }
+
+    #[rstest]
+    fn test_not_same_gene(kif21a: GeneDoc) {
+        let kif21b = GeneDoc::default()
+            .with_symbol("KIF21B")
+            .with_hgnc_id("HGNC:29442");
+
+        assert!(!kif21a.same_gene(&kif21b));
+        assert!(!kif21a.same_gene(&GeneDoc::default()));
+    }
+
+    #[rstest]
+    fn test_name() {
+        let gene_doc = GeneDoc::default()
+            .with_symbol("KIF21A")
+            .with_name("kinesin family member 21A");
+
+        assert_eq!(gene_doc.name(), Some("kinesin family member 21A"));
+        assert_eq!(GeneDoc::default().name(), None);
+    }
+
+    #[rstest]
+    fn test_locus_accessors() {
+        let gene_doc = GeneDoc::default()
+            .with_symbol("KIF21A")
+            .with_locus_type("gene with protein product")
+            .with_locus_group("protein-coding gene");
+
+        assert_eq!(gene_doc.locus_type(), Some("gene with protein product"));
+        assert_eq!(gene_doc.locus_group(), Some("protein-coding gene"));
+        assert!(gene_doc.is_protein_coding());
+    }
+
+    #[rstest]
+    #[case(GeneDoc::default().with_symbol("KIF21BP").with_locus_group("pseudogene"))]
+    #[case(GeneDoc::default().with_symbol("H19").with_locus_group("non-coding RNA"))]
+    #[case(GeneDoc::default().with_symbol("KIF21A"))]
+    fn test_is_not_protein_coding(#[case] gene_doc: GeneDoc) {
+        assert!(!gene_doc.is_protein_coding());
+    }
+
+    #[rstest]
+    fn test_mane_select_transcript() {
+        let gene_doc = GeneDoc::default()
+            .with_symbol("KIF21A")
+            .with_mane_select(vec![
+                "ENST00000361418.10".to_string(),
+                "NM_001173464.2".to_string(),
+            ]);
+
+        assert_eq!(
+            gene_doc.mane_select_transcript(),
+            Some("ENST00000361418.10")
+        );
+        assert_eq!(
+            gene_doc.mane_select_transcripts(),
+            ["ENST00000361418.10", "NM_001173464.2"]
+        );
+        assert_eq!(GeneDoc::default().mane_select_transcript(), None);
+    }
 }
@@ -119,6 +119,14 @@ mod tests {
                 .with_symbol("BRCA1"),
         );
 
+        docs.insert(
+            "KIF21A".to_string(),
+            GeneDoc::default()
+                .with_hgnc_id("HGNC:19349")
+                .with_symbol("KIF21A")
+                .with_location("12q12"),
+        );
+
         MockHGNCClient::new(docs)
     }
 
@@ -184,4 +192,15 @@ mod tests {
         assert_eq!(symbol, "BRCA1");
         assert_eq!(id, "HGNC:1100");
     }
+
+    #[test]
+    fn test_request_gene_data_location() {
+        let mock = setup_mock();
+
+        let doc = mock.request_gene_data(GeneQuery::Symbol("KIF21A")).unwrap();
+        assert_eq!(doc.location(), Some("12q12"));
+
+        let doc = mock.request_gene_data(GeneQuery::Symbol("BRCA1")).unwrap();
+        assert_eq!(doc.location(), None);
+    }
 }
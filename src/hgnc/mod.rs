@@ -9,6 +9,8 @@
 //! - `GeneQuery::Symbol(&str)` — query by gene symbol
 //! - `GeneQuery::HgncId(&str)` — query by HGNC ID
 //!
+//! A GeneQuery is displayed as e.g. `symbol:KIF21A` or `hgnc:HGNC:19349`, which can be parsed back with `GeneQuery::parse`.
+//!
 //! # [`GeneDoc`]
 //!
 //! The full data on the gene provided by HGNC.
@@ -24,7 +26,9 @@
 //!
 //! # [`HGNCClient`]
 //!
-//! The basic implementation of the HGNCData trait. Request a GeneDoc from the HGNC API.
+//! The basic implementation of the HGNCData trait. Request a GeneDoc from the HGNC API. Unsuccessful requests are retried a few times before giving up.
+//!
+//! To resolve a large number of gene symbols to HGNC IDs, use `resolve_symbols`, which combines them into a few search requests.
 //!
 //! # [`CachedHGNCClient`]
 //!
@@ -34,3 +34,9 @@ pub trait HGNCData: Debug {
         Ok((symbol, id))
     }
 }
+
+impl<T: HGNCData + ?Sized> HGNCData for Box<T> {
+    fn request_gene_data(&self, query: GeneQuery) -> Result<GeneDoc, HGNCError> {
+        (**self).request_gene_data(query)
+    }
+}
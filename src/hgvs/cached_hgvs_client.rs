@@ -1,16 +1,18 @@
 #![allow(unused)]
 
 use crate::caching::redb_cacher::RedbCacher;
+use crate::caching::traits::Cacher;
+use crate::hgvs::enums::{ChrNaming, GenomeAssembly};
 use crate::hgvs::error::HGVSError;
 use crate::hgvs::hgvs_client::HGVSClient;
 use crate::hgvs::hgvs_variant::HgvsVariant;
 use crate::hgvs::traits::HGVSData;
 use std::path::PathBuf;
 
-#[derive(Default, Debug)]
-pub struct CachedHGVSClient {
-    cacher: RedbCacher<HgvsVariant>,
-    hgvs_client: HGVSClient,
+#[derive(Debug)]
+pub struct CachedHGVSClient<D = HGVSClient, C = RedbCacher<HgvsVariant>> {
+    cacher: C,
+    hgvs_client: D,
 }
 
 impl CachedHGVSClient {
@@ -22,29 +24,112 @@ impl CachedHGVSClient {
             hgvs_client,
         })
     }
+
+    /// Constructs a CachedHGVSClient whose cache is in the cache directory of the OS, or in PIVOT_CACHE_DIR if that environment variable is set.
+    pub fn try_default() -> Result<Self, HGVSError> {
+        let cacher = RedbCacher::try_default()?;
+        cacher.init_cache()?;
+        Ok(CachedHGVSClient {
+            cacher,
+            hgvs_client: HGVSClient::default(),
+        })
+    }
 }
 
-impl HGVSData for CachedHGVSClient {
-    fn request_and_validate_hgvs(&self, unvalidated_hgvs: &str) -> Result<HgvsVariant, HGVSError> {
+impl<D: HGVSData> CachedHGVSClient<D, RedbCacher<HgvsVariant>> {
+    /// Every cached variant with its cache key, which has the form "{assembly}|{transcript_hgvs}".
+    pub fn all_cached_variants(&self) -> Result<Vec<(String, HgvsVariant)>, HGVSError> {
         let cache = self.cacher.open_cache()?;
-        if let Some(hgvs_variant) = self.cacher.find_cache_entry(unvalidated_hgvs, &cache) {
-            return Ok(hgvs_variant);
+        Ok(self.cacher.entries(&cache)?)
+    }
+
+    /// Checks that every cached variant can be read, returning how many are cached (an error lists any unreadable entries).
+    pub fn verify_cache(&self) -> Result<usize, HGVSError> {
+        Ok(self.cacher.verify()?)
+    }
+
+    /// Whether the variant is cached for the client's assembly. This never creates or modifies the cache.
+    pub fn is_cached(&self, unvalidated_hgvs: &str) -> Result<bool, HGVSError> {
+        Ok(self.cacher.contains(&HgvsVariant::cache_key(
+            &self.genome_assembly().to_string(),
+            unvalidated_hgvs,
+        ))?)
+    }
+}
+
+impl Default for CachedHGVSClient {
+    /// If the default cache can't be created, a cache in the temp directory is used instead.
+    /// Use `CachedHGVSClient::try_default` to handle this case as an error.
+    fn default() -> Self {
+        CachedHGVSClient {
+            cacher: RedbCacher::default_or_temp(),
+            hgvs_client: HGVSClient::default(),
+        }
+    }
+}
+
+impl<D: HGVSData, C: Cacher<HgvsVariant>> CachedHGVSClient<D, C> {
+    pub(crate) fn with_cacher(cacher: C, hgvs_client: D) -> Self {
+        CachedHGVSClient {
+            cacher,
+            hgvs_client,
+        }
+    }
+}
+
+impl<D: HGVSData, C: Cacher<HgvsVariant>> HGVSData for CachedHGVSClient<D, C> {
+    /// Chromosomes are cached with a chr prefix, so that clients with any ChrNaming can share a cache,
+    /// and the ChrNaming of the client is applied to the cached variant. So with ChrNaming::AsReturned, chromosomes have a chr prefix.
+    fn request_and_validate_hgvs(&self, unvalidated_hgvs: &str) -> Result<HgvsVariant, HGVSError> {
+        let cache_key =
+            HgvsVariant::cache_key(&self.genome_assembly().to_string(), unvalidated_hgvs);
+        if let Some(hgvs_variant) = self.cacher.get(&cache_key)? {
+            return Ok(hgvs_variant.with_chr_naming(self.chr_naming()));
         }
 
         let hgvs_variant = self
             .hgvs_client
-            .request_and_validate_hgvs(unvalidated_hgvs)?;
-        self.cacher.cache_object(hgvs_variant.clone(), &cache)?;
-        Ok(hgvs_variant.clone())
+            .request_and_validate_hgvs(unvalidated_hgvs)?
+            .with_chr_naming(ChrNaming::Prefixed);
+        self.cacher.insert(hgvs_variant.clone())?;
+        Ok(hgvs_variant.with_chr_naming(self.chr_naming()))
+    }
+
+    fn genome_assembly(&self) -> &GenomeAssembly {
+        self.hgvs_client.genome_assembly()
+    }
+
+    fn chr_naming(&self) -> ChrNaming {
+        self.hgvs_client.chr_naming()
+    }
+
+    /// Only variants on the client's own assembly are cached, so other assemblies are requested directly.
+    fn get_vcf_coordinates(
+        &self,
+        unvalidated_hgvs: &str,
+        assembly: GenomeAssembly,
+    ) -> Result<(String, u32, String, String), HGVSError> {
+        if assembly == *self.genome_assembly() {
+            Ok(self
+                .request_and_validate_hgvs(unvalidated_hgvs)?
+                .vcf_coordinates())
+        } else {
+            self.hgvs_client
+                .get_vcf_coordinates(unvalidated_hgvs, assembly)
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::caching::null_cacher::NullCacher;
     use crate::caching::traits::Cacheable;
-    use redb::{Database as RedbDatabase, ReadableDatabase};
+    use crate::hgvs::test_utils::load_fixture;
+    use redb::{Database as RedbDatabase, ReadableDatabase, TableDefinition};
     use rstest::{fixture, rstest};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
     use tempfile::TempDir;
 
     #[fixture]
@@ -52,6 +137,333 @@ mod tests {
         tempfile::tempdir().expect("Failed to create temporary directory")
     }
 
+    /// Counts how many requests reach it, so that cache hits and misses can be told apart.
+    #[derive(Debug)]
+    struct CountingHGVSClient {
+        n_requests: AtomicUsize,
+        genome_assembly: GenomeAssembly,
+        chr_naming: ChrNaming,
+    }
+
+    impl CountingHGVSClient {
+        fn new(genome_assembly: GenomeAssembly) -> Self {
+            CountingHGVSClient {
+                n_requests: AtomicUsize::new(0),
+                genome_assembly,
+                chr_naming: ChrNaming::AsReturned,
+            }
+        }
+
+        fn with_chr_naming(mut self, chr_naming: ChrNaming) -> Self {
+            self.chr_naming = chr_naming;
+            self
+        }
+    }
+
+    impl HGVSData for CountingHGVSClient {
+        fn request_and_validate_hgvs(
+            &self,
+            unvalidated_hgvs: &str,
+        ) -> Result<HgvsVariant, HGVSError> {
+            self.n_requests.fetch_add(1, Ordering::SeqCst);
+            let (transcript, allele) = unvalidated_hgvs.split_once(':').unwrap();
+            Ok(HgvsVariant::new_typed(
+                self.genome_assembly,
+                "chr12",
+                39332405,
+                "G",
+                "A",
+                "KIF21A",
+                "HGNC:19349",
+                transcript,
+                allele,
+                unvalidated_hgvs,
+                "NC_000012.12:g.39332405G>A",
+                None::<&str>,
+            )
+            .with_chr_naming(self.chr_naming))
+        }
+
+        fn genome_assembly(&self) -> &GenomeAssembly {
+            &self.genome_assembly
+        }
+
+        fn chr_naming(&self) -> ChrNaming {
+            self.chr_naming
+        }
+    }
+
+    #[rstest]
+    fn test_null_cacher_always_requests() {
+        let client = CachedHGVSClient::with_cacher(
+            NullCacher,
+            CountingHGVSClient::new(GenomeAssembly::Hg38),
+        );
+
+        client
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+        client
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+
+        assert_eq!(client.hgvs_client.n_requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[rstest]
+    fn test_redb_cacher_requests_once(temp_dir: TempDir) {
+        let cacher = RedbCacher::new(temp_dir.path().join("cache.hgvs"));
+        cacher.init_cache().unwrap();
+        let client =
+            CachedHGVSClient::with_cacher(cacher, CountingHGVSClient::new(GenomeAssembly::Hg38));
+
+        client
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+        client
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+
+        assert_eq!(client.hgvs_client.n_requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[rstest]
+    fn test_request_with_str_and_string(temp_dir: TempDir) {
+        let cacher = RedbCacher::new(temp_dir.path().join("cache.hgvs"));
+        cacher.init_cache().unwrap();
+        let client =
+            CachedHGVSClient::with_cacher(cacher, CountingHGVSClient::new(GenomeAssembly::Hg38));
+        // e.g. a HGVS string parsed from a file
+        let owned_hgvs = String::from("NM_001173464.1:c.2860C>T");
+
+        let hgvs_variant = client
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+        let cached_hgvs_variant = client.request_and_validate_hgvs(&owned_hgvs).unwrap();
+
+        assert_eq!(cached_hgvs_variant, hgvs_variant);
+        assert_eq!(client.hgvs_client.n_requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[rstest]
+    fn test_request_from_several_threads(temp_dir: TempDir) {
+        let cacher = RedbCacher::new(temp_dir.path().join("cache.hgvs"));
+        cacher.init_cache().unwrap();
+        let client =
+            CachedHGVSClient::with_cacher(cacher, CountingHGVSClient::new(GenomeAssembly::Hg38));
+        client
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+
+        thread::scope(|scope| {
+            for thread_index in 0..8 {
+                let client = &client;
+                scope.spawn(move || {
+                    for _ in 0..10 {
+                        client
+                            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+                            .unwrap();
+                        client
+                            .request_and_validate_hgvs(&format!(
+                                "NM_001173464.1:c.{}C>T",
+                                2870 + thread_index
+                            ))
+                            .unwrap();
+                    }
+                });
+            }
+        });
+
+        assert_eq!(client.all_cached_variants().unwrap().len(), 9);
+        // the shared variant was cached before the threads started, and each thread's own variant is only requested once
+        assert_eq!(client.hgvs_client.n_requests.load(Ordering::SeqCst), 9);
+    }
+
+    #[rstest]
+    fn test_all_cached_variants(temp_dir: TempDir) {
+        let cacher = RedbCacher::new(temp_dir.path().join("cache.hgvs"));
+        cacher.init_cache().unwrap();
+        let client =
+            CachedHGVSClient::with_cacher(cacher, CountingHGVSClient::new(GenomeAssembly::Hg38));
+        client
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+        client
+            .request_and_validate_hgvs("NM_001173464.1:c.2861G>A")
+            .unwrap();
+
+        assert!(client.is_cached("NM_001173464.1:c.2860C>T").unwrap());
+        assert!(!client.is_cached("NM_001173464.1:c.2862G>A").unwrap());
+        let cached_variants = client.all_cached_variants().unwrap();
+
+        assert_eq!(cached_variants.len(), 2);
+        assert_eq!(cached_variants[0].0, "hg38|NM_001173464.1:c.2860C>T");
+        assert_eq!(
+            cached_variants[1].1.transcript_hgvs(),
+            "NM_001173464.1:c.2861G>A"
+        );
+    }
+
+    #[rstest]
+    fn test_get_vcf_coordinates_other_assembly_err() {
+        let client = CachedHGVSClient::with_cacher(
+            NullCacher,
+            CountingHGVSClient::new(GenomeAssembly::Hg38),
+        );
+
+        let vcf_coordinates = client
+            .get_vcf_coordinates("NM_001173464.1:c.2860C>T", GenomeAssembly::Hg38)
+            .unwrap();
+        assert_eq!(vcf_coordinates.1, 39332405);
+
+        let result = client.get_vcf_coordinates("NM_001173464.1:c.2860C>T", GenomeAssembly::Hg19);
+        assert!(matches!(
+            result,
+            Err(HGVSError::GenomeAssemblyNotFound { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_cache_is_per_assembly(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.hgvs");
+        let hg38_cacher = RedbCacher::new(cache_file_path.clone());
+        hg38_cacher.init_cache().unwrap();
+        let hg38_client = CachedHGVSClient::with_cacher(
+            hg38_cacher,
+            CountingHGVSClient::new(GenomeAssembly::Hg38),
+        );
+        hg38_client
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+
+        let hg19_client = CachedHGVSClient::with_cacher(
+            RedbCacher::new(cache_file_path),
+            CountingHGVSClient::new(GenomeAssembly::Hg19),
+        );
+        let hgvs_variant = hg19_client
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+
+        assert_eq!(hg19_client.hgvs_client.n_requests.load(Ordering::SeqCst), 1);
+        assert_eq!(hgvs_variant.assembly(), "hg19");
+    }
+
+    #[rstest]
+    fn test_chr_naming_is_applied_to_cached_variants(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.hgvs");
+        let bare_cacher = RedbCacher::new(cache_file_path.clone());
+        bare_cacher.init_cache().unwrap();
+        let bare_client = CachedHGVSClient::with_cacher(
+            bare_cacher,
+            CountingHGVSClient::new(GenomeAssembly::Hg38).with_chr_naming(ChrNaming::Bare),
+        );
+        let hgvs_variant = bare_client
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+        assert_eq!(hgvs_variant.chr(), "12");
+
+        let prefixed_client = CachedHGVSClient::with_cacher(
+            RedbCacher::new(cache_file_path),
+            CountingHGVSClient::new(GenomeAssembly::Hg38).with_chr_naming(ChrNaming::Prefixed),
+        );
+        let hgvs_variant = prefixed_client
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+
+        assert_eq!(
+            prefixed_client
+                .hgvs_client
+                .n_requests
+                .load(Ordering::SeqCst),
+            0
+        );
+        assert_eq!(hgvs_variant.chr(), "chr12");
+    }
+
+    #[rstest]
+    fn test_clients_share_cache_file(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.hgvs");
+        let client = CachedHGVSClient::new(cache_file_path.clone(), HGVSClient::default()).unwrap();
+        let other_client =
+            CachedHGVSClient::new(cache_file_path.clone(), HGVSClient::default()).unwrap();
+        let hgvs_variant = CountingHGVSClient::new(GenomeAssembly::Hg38)
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+
+        client.cacher.insert(hgvs_variant).unwrap();
+
+        assert!(other_client.is_cached("NM_001173464.1:c.2860C>T").unwrap());
+        assert_eq!(other_client.all_cached_variants().unwrap().len(), 1);
+    }
+
+    #[rstest]
+    fn test_selected_assembly_fallback_is_cached(temp_dir: TempDir) {
+        let unvalidated_hgvs = "NM_001173464.1:c.2860C>T";
+        let mut response = load_fixture::<serde_json::Value>("kif21a");
+        let loci = response[unvalidated_hgvs]["primary_assembly_loci"]
+            .as_object_mut()
+            .unwrap();
+        loci.remove("hg38");
+        loci.remove("hg19");
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_body(response.to_string())
+            .expect(1)
+            .create();
+        let hgvs_client = HGVSClient::default()
+            .with_api_url(server.url())
+            .with_selected_assembly_fallback(true);
+        let client =
+            CachedHGVSClient::new(temp_dir.path().join("cache.hgvs"), hgvs_client).unwrap();
+
+        let hgvs_variant = client.request_and_validate_hgvs(unvalidated_hgvs).unwrap();
+        let cached_hgvs_variant = client.request_and_validate_hgvs(unvalidated_hgvs).unwrap();
+
+        mock.assert();
+        assert_eq!(hgvs_variant.assembly(), "hg38");
+        assert_eq!(cached_hgvs_variant, hgvs_variant);
+        assert!(client.is_cached(unvalidated_hgvs).unwrap());
+    }
+
+    #[rstest]
+    fn test_migrate_cache_key_without_assembly(temp_dir: TempDir) {
+        let cache_file_path = temp_dir.path().join("cache.hgvs");
+        let hgvs_variant = CountingHGVSClient::new(GenomeAssembly::Hg38)
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+        // a cache of schema version 1, whose keys had no assembly
+        let cache = RedbDatabase::create(&cache_file_path).unwrap();
+        let cache_writer = cache.begin_write().unwrap();
+        {
+            let mut table = cache_writer
+                .open_table(TableDefinition::<&str, HgvsVariant>::new(
+                    HgvsVariant::TABLE_NAME,
+                ))
+                .unwrap();
+            table
+                .insert("NM_001173464.1:c.2860C>T", hgvs_variant)
+                .unwrap();
+            let mut version_table = cache_writer
+                .open_table(TableDefinition::<&str, u32>::new("schema_version"))
+                .unwrap();
+            version_table.insert(HgvsVariant::TABLE_NAME, 1).unwrap();
+        }
+        cache_writer.commit().unwrap();
+        drop(cache);
+
+        let cacher = RedbCacher::new(cache_file_path);
+        cacher.init_cache().unwrap();
+        let client =
+            CachedHGVSClient::with_cacher(cacher, CountingHGVSClient::new(GenomeAssembly::Hg38));
+
+        client
+            .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+            .unwrap();
+        assert_eq!(client.hgvs_client.n_requests.load(Ordering::SeqCst), 0);
+        assert_eq!(client.all_cached_variants().unwrap().len(), 1);
+    }
+
     #[rstest]
     fn test_cached_hgvs_client(temp_dir: TempDir) {
         let cache_file_path = temp_dir.path().join("cache.hgvs");
@@ -66,7 +478,7 @@ mod tests {
         let cache = cached_client.cacher.open_cache().unwrap();
         let cached_hgvs = cached_client
             .cacher
-            .find_cache_entry(unvalidated_hgvs, &cache)
+            .find_cache_entry(&HgvsVariant::cache_key("hg38", unvalidated_hgvs), &cache)
             .unwrap();
         assert_eq!(cached_hgvs.transcript_hgvs(), unvalidated_hgvs);
     }
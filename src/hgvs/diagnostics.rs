@@ -0,0 +1,57 @@
+use crate::hgvs::error::HGVSError;
+use crate::hgvs::validation_warning::ValidationWarning;
+
+/// What VariantValidator reported about a variant, separated into fatal problems, which stopped the variant from being validated,
+/// and advisory warnings about a variant which was validated nonetheless.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Diagnostics {
+    /// The problems which stopped the variant from being validated, e.g. a wrong reference base
+    fatal_problems: Vec<String>,
+    /// The warnings about a validated variant, e.g. a TranscriptVersionWarning
+    warnings: Vec<ValidationWarning>,
+}
+
+impl Diagnostics {
+    pub fn new(fatal_problems: Vec<String>, warnings: Vec<ValidationWarning>) -> Self {
+        Diagnostics {
+            fatal_problems,
+            warnings,
+        }
+    }
+
+    /// The diagnostics of a variant which was validated with the given validation warnings.
+    pub(crate) fn from_validation_warnings(validation_warnings: Vec<ValidationWarning>) -> Self {
+        Diagnostics::new(vec![], validation_warnings)
+    }
+
+    /// The diagnostics of a variant which could not be validated.
+    ///
+    /// Only the problems which VariantValidator found with the variant are fatal problems,
+    /// so errors such as a failed request give no diagnostics.
+    pub fn from_error(err: &HGVSError) -> Self {
+        let fatal_problems = match err {
+            HGVSError::InvalidHgvs { problems, .. } => problems.clone(),
+            HGVSError::ReferenceBaseMismatch { .. } => vec![err.to_string()],
+            _ => vec![],
+        };
+        Diagnostics::new(fatal_problems, vec![])
+    }
+
+    pub fn fatal_problems(&self) -> &[String] {
+        &self.fatal_problems
+    }
+
+    pub fn warnings(&self) -> &[ValidationWarning] {
+        &self.warnings
+    }
+
+    /// Whether VariantValidator found a problem which stopped the variant from being validated.
+    pub fn is_fatal(&self) -> bool {
+        !self.fatal_problems.is_empty()
+    }
+
+    /// Whether VariantValidator reported neither fatal problems nor warnings.
+    pub fn is_empty(&self) -> bool {
+        self.fatal_problems.is_empty() && self.warnings.is_empty()
+    }
+}
@@ -0,0 +1,73 @@
+use crate::hgvs::error::HGVSError;
+use crate::hgvs::genomic_locus::GenomicLocus;
+use crate::hgvs::hgvs_variant::HgvsVariant;
+use serde::{Deserialize, Serialize};
+
+/// A variant with its genomic locus on two genome assemblies, as listed in clinical reports which give both GRCh37 and GRCh38 coordinates.
+///
+/// The transcript, gene and protein data are those of the primary HgvsVariant.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DualAssemblyVariant {
+    /// The variant on the primary genome build
+    primary: HgvsVariant,
+    /// Secondary genome build, e.g., hg19
+    secondary_assembly: String,
+    /// Chromosome on the secondary genome build
+    secondary_chr: String,
+    /// Position on the chromosome of the secondary genome build
+    secondary_position: u32,
+    /// Reference allele on the secondary genome build
+    secondary_ref_allele: String,
+    /// Alternate allele on the secondary genome build
+    secondary_alt_allele: String,
+    /// Genomic HGVS nomenclature on the secondary genome build, e.g., NC_000012.11:g.39726207G>A
+    secondary_g_hgvs: String,
+}
+
+impl DualAssemblyVariant {
+    /// Keeps only the genomic data of the secondary variant, which should be the same variant on another assembly.
+    pub(crate) fn from_variants(primary: HgvsVariant, secondary: HgvsVariant) -> Self {
+        let (secondary_chr, secondary_position, secondary_ref_allele, secondary_alt_allele) =
+            secondary.vcf_coordinates();
+        DualAssemblyVariant {
+            secondary_assembly: secondary.assembly().to_string(),
+            secondary_chr,
+            secondary_position,
+            secondary_ref_allele,
+            secondary_alt_allele,
+            secondary_g_hgvs: secondary.g_hgvs().to_string(),
+            primary,
+        }
+    }
+
+    pub fn primary(&self) -> &HgvsVariant {
+        &self.primary
+    }
+
+    pub fn secondary_assembly(&self) -> &str {
+        self.secondary_assembly.as_ref()
+    }
+
+    pub fn secondary_g_hgvs(&self) -> &str {
+        self.secondary_g_hgvs.as_ref()
+    }
+
+    /// The (chr, pos, ref, alt) of the variant on the secondary assembly.
+    pub fn secondary_vcf_coordinates(&self) -> (String, u32, String, String) {
+        (
+            self.secondary_chr.clone(),
+            self.secondary_position,
+            self.secondary_ref_allele.clone(),
+            self.secondary_alt_allele.clone(),
+        )
+    }
+
+    pub fn secondary_genomic_locus(&self) -> Result<GenomicLocus, HGVSError> {
+        Ok(GenomicLocus::new(
+            self.secondary_assembly.parse()?,
+            self.secondary_chr.clone(),
+            self.secondary_position,
+        ))
+    }
+}
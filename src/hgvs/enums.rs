@@ -1,7 +1,9 @@
 #![allow(clippy::upper_case_acronyms)]
 
 use crate::hgvs::HGVSError;
+use crate::hgvs::utils::{is_c_hgvs, is_m_hgvs, is_n_hgvs};
 use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum ChromosomalSex {
@@ -37,12 +39,119 @@ impl TryFrom<u8> for AlleleCount {
     }
 }
 
-#[derive(Debug)]
+/// The HGVS syntax of a transcript-level variant, as given by the prefix of its allele.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HgvsSyntax {
+    /// c. variants on a coding transcript
+    Coding,
+    /// n. variants on a non-coding transcript
+    NonCoding,
+    /// m. variants on the mitochondrial genome
+    Mitochondrial,
+}
+
+impl HgvsSyntax {
+    pub fn from_allele(allele: &str) -> Option<Self> {
+        if is_c_hgvs(allele) {
+            Some(HgvsSyntax::Coding)
+        } else if is_n_hgvs(allele) {
+            Some(HgvsSyntax::NonCoding)
+        } else if is_m_hgvs(allele) {
+            Some(HgvsSyntax::Mitochondrial)
+        } else {
+            None
+        }
+    }
+
+    /// The syntax as used in a phenopacket Expression, e.g., "hgvs.c".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HgvsSyntax::Coding => "hgvs.c",
+            HgvsSyntax::NonCoding => "hgvs.n",
+            HgvsSyntax::Mitochondrial => "hgvs.m",
+        }
+    }
+}
+
+/// The class of a variant, as given by the edit of its HGVS allele.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariantClass {
+    /// a single nucleotide substitution, e.g., c.2860C>T
+    Snv,
+    /// a substitution of several nucleotides, e.g., c.2860_2861CG>TA
+    Mnv,
+    /// e.g., c.11031_11032delGA
+    Deletion,
+    /// e.g., c.2860_2861insA
+    Insertion,
+    /// e.g., c.2860dup
+    Duplication,
+    /// e.g., c.2860_2861delinsA
+    Delins,
+    /// e.g., c.2860_2870inv
+    Inversion,
+    /// an edit which is not one of the above, e.g., an unchanged allele c.2860=
+    Unknown,
+}
+
+impl VariantClass {
+    pub fn from_allele(allele: &str) -> Self {
+        let edit = allele.split_once('.').map_or(allele, |(_, edit)| edit);
+        if edit.contains("delins") {
+            VariantClass::Delins
+        } else if edit.contains("dup") {
+            VariantClass::Duplication
+        } else if edit.contains("inv") {
+            VariantClass::Inversion
+        } else if edit.contains("del") {
+            VariantClass::Deletion
+        } else if edit.contains("ins") {
+            VariantClass::Insertion
+        } else if let Some((position_and_ref, alt)) = edit.split_once('>') {
+            let ref_bases = position_and_ref
+                .chars()
+                .rev()
+                .take_while(char::is_ascii_alphabetic)
+                .count();
+            match (ref_bases, alt.len()) {
+                (1, 1) => VariantClass::Snv,
+                (0, _) | (_, 0) => VariantClass::Unknown,
+                _ => VariantClass::Mnv,
+            }
+        } else {
+            VariantClass::Unknown
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum GenomeAssembly {
     Hg38,
     Hg19,
 }
 
+impl GenomeAssembly {
+    /// Every supported genome assembly.
+    pub fn all() -> &'static [GenomeAssembly] {
+        &[GenomeAssembly::Hg38, GenomeAssembly::Hg19]
+    }
+}
+
+impl FromStr for GenomeAssembly {
+    type Err = HGVSError;
+
+    /// Parses an assembly name as used by VariantValidator, e.g., hg38 or GRCh38.
+    fn from_str(assembly: &str) -> Result<Self, Self::Err> {
+        match assembly.to_lowercase().as_str() {
+            "hg38" | "grch38" => Ok(GenomeAssembly::Hg38),
+            "hg19" | "grch37" => Ok(GenomeAssembly::Hg19),
+            _ => Err(HGVSError::UnknownGenomeAssembly {
+                assembly: assembly.to_string(),
+            }),
+        }
+    }
+}
+
 impl Display for GenomeAssembly {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let str = match self {
@@ -52,3 +161,193 @@ impl Display for GenomeAssembly {
         write!(f, "{}", str)
     }
 }
+
+/// How the chromosome of a HgvsVariant is named.
+///
+/// VariantValidator names chromosomes differently depending on the assembly, e.g. chr12 on hg38, but 12 on grch38.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChrNaming {
+    /// as given by VariantValidator
+    #[default]
+    AsReturned,
+    /// always with a chr prefix, e.g., chr12 or chrM
+    Prefixed,
+    /// never with a chr prefix, e.g., 12 or MT
+    Bare,
+}
+
+impl ChrNaming {
+    pub fn apply(&self, chr: &str) -> String {
+        let bare_chr = chr.strip_prefix("chr").unwrap_or(chr);
+        match self {
+            ChrNaming::AsReturned => chr.to_string(),
+            ChrNaming::Prefixed if bare_chr == "MT" => "chrM".to_string(),
+            ChrNaming::Prefixed => format!("chr{bare_chr}"),
+            ChrNaming::Bare if bare_chr == "M" => "MT".to_string(),
+            ChrNaming::Bare => bare_chr.to_string(),
+        }
+    }
+}
+
+/// The flag of a VariantValidator response, which indicates the kind of variant information it contains.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResponseFlag {
+    /// a variant on a gene transcript
+    GeneVariant,
+    /// a variant on the mitochondrial genome
+    Mitochondrial,
+    /// a genomic variant which does not lie on any gene transcript
+    Intergenic,
+    /// the variant could not be validated, and the response contains the validation warnings
+    Warning,
+    /// a flag which PIVOT does not know about
+    Other(String),
+}
+
+impl ResponseFlag {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ResponseFlag::GeneVariant => "gene_variant",
+            ResponseFlag::Mitochondrial => "mitochondrial",
+            ResponseFlag::Intergenic => "intergenic",
+            ResponseFlag::Warning => "warning",
+            ResponseFlag::Other(flag) => flag,
+        }
+    }
+}
+
+impl From<&str> for ResponseFlag {
+    fn from(flag: &str) -> Self {
+        match flag {
+            "gene_variant" => ResponseFlag::GeneVariant,
+            "mitochondrial" => ResponseFlag::Mitochondrial,
+            "intergenic" => ResponseFlag::Intergenic,
+            "warning" => ResponseFlag::Warning,
+            _ => ResponseFlag::Other(flag.to_string()),
+        }
+    }
+}
+
+impl Display for ResponseFlag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hgvs::HgvsVariant;
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    fn kif21a_variant(assembly: GenomeAssembly, position: u32) -> HgvsVariant {
+        HgvsVariant::new_typed(
+            assembly,
+            "chr12",
+            position,
+            "G",
+            "A",
+            "KIF21A",
+            "HGNC:19349",
+            "NM_001173464.1",
+            "c.2860C>T",
+            "NM_001173464.1:c.2860C>T",
+            "NC_000012.12:g.39332405G>A",
+            None::<&str>,
+        )
+    }
+
+    #[rstest]
+    #[case("hg38", GenomeAssembly::Hg38)]
+    #[case("GRCh38", GenomeAssembly::Hg38)]
+    #[case("hg19", GenomeAssembly::Hg19)]
+    #[case("grch37", GenomeAssembly::Hg19)]
+    fn test_genome_assembly_from_str(#[case] assembly: &str, #[case] expected: GenomeAssembly) {
+        assert_eq!(assembly.parse::<GenomeAssembly>().unwrap(), expected);
+    }
+
+    #[rstest]
+    fn test_genome_assembly_all() {
+        // a new GenomeAssembly makes this match non-exhaustive, as a reminder to add it to GenomeAssembly::all
+        let n_assemblies = match GenomeAssembly::Hg38 {
+            GenomeAssembly::Hg38 | GenomeAssembly::Hg19 => 2,
+        };
+
+        assert_eq!(GenomeAssembly::all().len(), n_assemblies);
+        let assembly_names = GenomeAssembly::all()
+            .iter()
+            .map(|assembly| assembly.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(assembly_names, vec!["hg38", "hg19"]);
+    }
+
+    #[rstest]
+    fn test_genome_assembly_from_str_err() {
+        assert!(matches!(
+            "hg18".parse::<GenomeAssembly>(),
+            Err(HGVSError::UnknownGenomeAssembly { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case(ChrNaming::AsReturned, "12", "12")]
+    #[case(ChrNaming::AsReturned, "chr12", "chr12")]
+    #[case(ChrNaming::Prefixed, "12", "chr12")]
+    #[case(ChrNaming::Prefixed, "chrX", "chrX")]
+    #[case(ChrNaming::Prefixed, "MT", "chrM")]
+    #[case(ChrNaming::Bare, "chr12", "12")]
+    #[case(ChrNaming::Bare, "X", "X")]
+    #[case(ChrNaming::Bare, "chrM", "MT")]
+    fn test_chr_naming(#[case] chr_naming: ChrNaming, #[case] chr: &str, #[case] expected: &str) {
+        assert_eq!(chr_naming.apply(chr), expected);
+    }
+
+    #[rstest]
+    #[case("gene_variant", ResponseFlag::GeneVariant)]
+    #[case("mitochondrial", ResponseFlag::Mitochondrial)]
+    #[case("intergenic", ResponseFlag::Intergenic)]
+    #[case("warning", ResponseFlag::Warning)]
+    #[case("empty_result", ResponseFlag::Other("empty_result".to_string()))]
+    fn test_response_flag_from_str(#[case] flag: &str, #[case] expected: ResponseFlag) {
+        let response_flag = ResponseFlag::from(flag);
+        assert_eq!(response_flag, expected);
+        assert_eq!(response_flag.as_str(), flag);
+    }
+
+    #[rstest]
+    #[case("c.2860C>T", VariantClass::Snv)]
+    #[case("n.601G>T", VariantClass::Snv)]
+    #[case("c.2860_2861CG>TA", VariantClass::Mnv)]
+    #[case("c.11031_11032delGA", VariantClass::Deletion)]
+    #[case("c.2860del", VariantClass::Deletion)]
+    #[case("c.2860dup", VariantClass::Duplication)]
+    #[case("c.2860_2861insA", VariantClass::Insertion)]
+    #[case("c.2860_2861delinsA", VariantClass::Delins)]
+    #[case("c.2860_2870inv", VariantClass::Inversion)]
+    #[case("c.2860=", VariantClass::Unknown)]
+    fn test_variant_class_from_allele(#[case] allele: &str, #[case] expected: VariantClass) {
+        assert_eq!(VariantClass::from_allele(allele), expected);
+    }
+
+    #[rstest]
+    fn test_genome_assembly_as_hash_map_key() {
+        let mut variants_by_assembly = HashMap::new();
+        for (assembly, position) in [
+            (GenomeAssembly::Hg38, 39332405),
+            (GenomeAssembly::Hg19, 39726207),
+        ] {
+            variants_by_assembly.insert(assembly, kif21a_variant(assembly, position));
+        }
+
+        assert_eq!(variants_by_assembly.len(), 2);
+        assert_eq!(
+            variants_by_assembly[&GenomeAssembly::Hg19].position(),
+            39726207
+        );
+        assert_eq!(
+            variants_by_assembly[&GenomeAssembly::Hg38].assembly(),
+            "hg38"
+        );
+    }
+}
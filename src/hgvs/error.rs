@@ -1,6 +1,8 @@
 use crate::caching::error::CacherError;
+use crate::hgnc::HGNCError;
 use crate::hgvs::enums::{AlleleCount, ChromosomalSex};
 use redb::{CommitError, DatabaseError, StorageError, TableError, TransactionError};
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -9,8 +11,22 @@ pub enum HGVSError {
         "Variant Validator did not accept submitted HGVS {hgvs}. Validation warnings: {problems:?}"
     )]
     InvalidHgvs { hgvs: String, problems: Vec<String> },
+    #[error(
+        "The reference base {submitted_ref} of Hgvs string {hgvs} does not agree with the reference sequence, which has {actual_ref}."
+    )]
+    ReferenceBaseMismatch {
+        hgvs: String,
+        submitted_ref: String,
+        actual_ref: String,
+    },
     #[error("Hgvs string {hgvs} not accepted due to format problem: {problem}.")]
     HgvsFormatNotAccepted { hgvs: String, problem: String },
+    #[error(
+        "Hgvs string {hgvs} is protein-level HGVS, which cannot be validated to genomic coordinates. Please use the corresponding c. HGVS on the transcript instead."
+    )]
+    ProteinHgvsNotSupported { hgvs: String },
+    #[error("VariantValidator found that {hgvs} is intergenic, so it has no gene or transcript.")]
+    IntergenicVariant { hgvs: String },
     #[error(
         "VariantValidator response for {hgvs} had a disallowed flag type {flag}. The allowed flag types are: {allowed_flags:?}"
     )]
@@ -19,6 +35,12 @@ pub enum HGVSError {
         flag: String,
         allowed_flags: Vec<String>,
     },
+    #[error("Could not parse HgvsVariant from TSV row '{row}': {problem}")]
+    InvalidTsvRow { row: String, problem: String },
+    #[error("Genome assembly {assembly} is not supported.")]
+    UnknownGenomeAssembly { assembly: String },
+    #[error("Transcript {current} is outdated. The latest version is {latest}.")]
+    OutdatedTranscript { current: String, latest: String },
     #[error(
         "VariantValidator response for {hgvs} did not have genome_assembly {desired_assembly}. The following assemblies were found instead: {found_assemblies:?}"
     )]
@@ -27,6 +49,15 @@ pub enum HGVSError {
         desired_assembly: String,
         found_assemblies: Vec<String>,
     },
+    #[error(
+        "VariantValidator response for {hgvs} only has a locus on {available_assembly} (it was selected under {selected_assembly}), so there is no locus on {desired_assembly}. Request the variant on {desired_assembly} instead."
+    )]
+    GenomeAssemblyMismatch {
+        hgvs: String,
+        desired_assembly: String,
+        available_assembly: String,
+        selected_assembly: String,
+    },
     #[error(
         "The provided {id_type} {inputted_gene} does not match with the actual gene {actual_gene} of HGVS variant {hgvs}"
     )]
@@ -36,6 +67,15 @@ pub enum HGVSError {
         hgvs: String,
         actual_gene: String,
     },
+    #[error(
+        "HGVS variants {hgvs1} and {hgvs2} can't be compound heterozygous, as they are in different genes {gene1} and {gene2}"
+    )]
+    CompoundHetGenesDiffer {
+        hgvs1: String,
+        gene1: String,
+        hgvs2: String,
+        gene2: String,
+    },
     #[error(
         "VariantValidator response for {hgvs} has element {element} with following problem: {problem}"
     )]
@@ -63,11 +103,33 @@ pub enum HGVSError {
         "VariantValidatorAPI returned an error on {attempts} attempts to retrieve data about variant {hgvs}"
     )]
     VariantValidatorAPI { hgvs: String, attempts: usize },
+    #[error(
+        "VariantValidator response contained information on {n_found} variants, when exactly one was expected."
+    )]
+    WrongNumberOfVariantInfos { n_found: usize },
     #[error("VariantValidator response for {hgvs} had an unexpected format: {format_issue}")]
     VariantValidatorResponseUnexpectedFormat { hgvs: String, format_issue: String },
+    #[error("VariantValidator could not find transcripts for gene {gene}. Problem: {problem}")]
+    GeneTranscriptsNotFound { gene: String, problem: String },
+    #[error("VariantValidator could not be reached at {url}: {problem}")]
+    Unreachable { url: String, problem: String },
+    #[error(
+        "The rate limit for VariantValidator requests was reached before requesting {hgvs}. Try again in {retry_after:?}."
+    )]
+    RateLimited { hgvs: String, retry_after: Duration },
+    #[error(
+        "VariantValidator responded to the request for {hgvs} with status {status} and content type '{content_type}' instead of JSON. It may be unavailable."
+    )]
+    NonJsonResponse {
+        hgvs: String,
+        status: u16,
+        content_type: String,
+    },
     #[error("VariantValidator fetch request for {hgvs} failed. Error: {err}.")]
     FetchRequest { hgvs: String, err: String },
     #[error(transparent)]
+    Hgnc(#[from] HGNCError),
+    #[error(transparent)]
     CacheDatabase(#[from] DatabaseError),
     #[error(transparent)]
     CacheTransaction(#[from] TransactionError),
@@ -0,0 +1,108 @@
+use crate::hgvs::enums::GenomeAssembly;
+use std::cmp::Ordering;
+
+/// A position on a chromosome of a genome assembly.
+///
+/// Loci are ordered by assembly, then by chromosome (1-22, X, Y, then mitochondrial), then by position.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GenomicLocus {
+    /// Genome build, e.g., hg38
+    assembly: GenomeAssembly,
+    /// Chromosome, e.g., chr12
+    chr: String,
+    /// Position on the chromosome
+    pos: u32,
+}
+
+impl GenomicLocus {
+    pub fn new(assembly: GenomeAssembly, chr: impl Into<String>, pos: u32) -> Self {
+        GenomicLocus {
+            assembly,
+            chr: chr.into(),
+            pos,
+        }
+    }
+
+    pub fn assembly(&self) -> GenomeAssembly {
+        self.assembly
+    }
+
+    pub fn chr(&self) -> &str {
+        self.chr.as_ref()
+    }
+
+    pub fn pos(&self) -> u32 {
+        self.pos
+    }
+
+    /// The rank of the chromosome in the canonical ordering, regardless of whether it has a "chr" prefix.
+    fn chr_rank(&self) -> (u32, &str) {
+        let chr = self.chr.strip_prefix("chr").unwrap_or(&self.chr);
+        match chr {
+            "X" => (23, ""),
+            "Y" => (24, ""),
+            "M" | "MT" => (25, ""),
+            _ => match chr.parse::<u32>() {
+                Ok(number) => (number, ""),
+                Err(_) => (u32::MAX, chr),
+            },
+        }
+    }
+}
+
+impl Ord for GenomicLocus {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.assembly
+            .to_string()
+            .cmp(&other.assembly.to_string())
+            .then_with(|| self.chr_rank().cmp(&other.chr_rank()))
+            .then_with(|| self.pos.cmp(&other.pos))
+            .then_with(|| self.chr.cmp(&other.chr))
+    }
+}
+
+impl PartialOrd for GenomicLocus {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_sort_genomic_loci() {
+        let mut loci = vec![
+            GenomicLocus::new(GenomeAssembly::Hg38, "chrX", 100),
+            GenomicLocus::new(GenomeAssembly::Hg38, "chr12", 39332405),
+            GenomicLocus::new(GenomeAssembly::Hg38, "chrM", 3243),
+            GenomicLocus::new(GenomeAssembly::Hg38, "chr2", 500),
+            GenomicLocus::new(GenomeAssembly::Hg38, "chr12", 100),
+            GenomicLocus::new(GenomeAssembly::Hg19, "chr12", 39726207),
+        ];
+
+        loci.sort();
+
+        assert_eq!(
+            loci,
+            vec![
+                GenomicLocus::new(GenomeAssembly::Hg19, "chr12", 39726207),
+                GenomicLocus::new(GenomeAssembly::Hg38, "chr2", 500),
+                GenomicLocus::new(GenomeAssembly::Hg38, "chr12", 100),
+                GenomicLocus::new(GenomeAssembly::Hg38, "chr12", 39332405),
+                GenomicLocus::new(GenomeAssembly::Hg38, "chrX", 100),
+                GenomicLocus::new(GenomeAssembly::Hg38, "chrM", 3243),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_chr_prefix_is_ignored_when_ordering() {
+        let with_prefix = GenomicLocus::new(GenomeAssembly::Hg38, "chr10", 1);
+        let without_prefix = GenomicLocus::new(GenomeAssembly::Hg38, "9", 2);
+
+        assert!(without_prefix < with_prefix);
+    }
+}
@@ -1,44 +1,51 @@
 #![allow(unused)]
 
-use crate::hgvs::enums::GenomeAssembly;
+use crate::hgvs::enums::{ChrNaming, GenomeAssembly, ResponseFlag};
 use crate::hgvs::error::HGVSError;
 use crate::hgvs::hgvs_variant::HgvsVariant;
-use crate::hgvs::json_schema::{SingleVariantInfo, VariantValidatorResponse};
+use crate::hgvs::json_schema::{
+    Gene2TranscriptsResponse, SingleVariantInfo, SingleVariantResponse, VariantValidatorResponse,
+};
 use crate::hgvs::traits::HGVSData;
-use crate::hgvs::utils::{is_c_hgvs, is_m_hgvs, is_n_hgvs};
+use crate::hgvs::transcript_info::TranscriptInfo;
+use crate::hgvs::utils::{
+    get_transcript_and_allele, is_c_hgvs, is_g_hgvs, is_m_hgvs, is_n_hgvs, is_p_hgvs,
+};
+use crate::hgvs::validation_warning::ValidationWarning;
+use log::warn;
 use ratelimit::Ratelimiter;
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::CONTENT_TYPE;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
+use std::cell::Cell;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::string::ToString;
 use std::thread::sleep;
 use std::time::Duration;
 
-const ALLOWED_FLAGS: [&str; 2] = ["gene_variant", "mitochondrial"];
+const ALLOWED_FLAGS: [ResponseFlag; 2] = [ResponseFlag::GeneVariant, ResponseFlag::Mitochondrial];
 
 pub struct HGVSClient {
-    rate_limiter: Ratelimiter,
+    rate_limiter: Option<Ratelimiter>,
     attempts: usize,
     api_url: String,
     client: Client,
     genome_assembly: GenomeAssembly,
+    fall_back_to_selected_assembly: bool,
+    strict: bool,
+    chr_naming: ChrNaming,
+    /// Overrides the URL of the tools endpoints, which is otherwise on the same VariantValidator instance as api_url
+    tools_url: Option<String>,
+    /// Overrides the URL requested by ping, which is otherwise on the same VariantValidator instance as api_url
+    ping_url: Option<String>,
+    extra_headers: HashMap<String, String>,
 }
 
 impl Default for HGVSClient {
     fn default() -> Self {
-        let rate_limiter = Ratelimiter::builder(2, Duration::from_secs(1))
-            .max_tokens(2)
-            .build()
-            .expect("Building rate limiter failed");
-        let api_url =
-            "https://rest.variantvalidator.org/VariantValidator/variantvalidator/".to_string();
-        HGVSClient::new(
-            rate_limiter,
-            3,
-            api_url.to_string(),
-            Client::new(),
-            GenomeAssembly::Hg38,
-        )
+        HGVSClient::with_shared_client(Client::new())
     }
 }
 
@@ -49,6 +56,7 @@ impl Debug for HGVSClient {
             .field("api_url", &self.api_url)
             .field("client", &self.client) // cannot debug-print
             .field("genome_assembly", &self.genome_assembly)
+            .field("extra_headers", &self.extra_headers.keys()) // values may be secret
             .finish()
     }
 }
@@ -62,14 +70,133 @@ impl HGVSClient {
         genome_assembly: GenomeAssembly,
     ) -> Self {
         HGVSClient {
-            rate_limiter,
+            rate_limiter: Some(rate_limiter),
             attempts,
             api_url,
             client,
             genome_assembly,
+            fall_back_to_selected_assembly: false,
+            strict: false,
+            chr_naming: ChrNaming::default(),
+            tools_url: None,
+            ping_url: None,
+            extra_headers: HashMap::new(),
         }
     }
 
+    /// Sets headers (e.g. Authorization) which are sent with every request, in addition to the default User-Agent and Accept headers.
+    /// This is useful when validating against a private VariantValidator instance.
+    pub fn with_extra_headers(mut self, extra_headers: HashMap<String, String>) -> Self {
+        self.extra_headers = extra_headers;
+        self
+    }
+
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into();
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, rate_limiter: Ratelimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Removes the rate limiter, e.g. for a local VariantValidator instance without a quota.
+    pub fn without_rate_limit(mut self) -> Self {
+        self.rate_limiter = None;
+        self
+    }
+
+    /// Sets the URL of the VariantValidator tools endpoints (e.g. gene2transcripts),
+    /// for when they are not on the same VariantValidator instance as the api_url.
+    pub fn with_tools_url(mut self, tools_url: impl Into<String>) -> Self {
+        self.tools_url = Some(tools_url.into());
+        self
+    }
+
+    /// Sets the URL requested by `ping`, for when it is not on the same VariantValidator instance as the api_url.
+    pub fn with_ping_url(mut self, ping_url: impl Into<String>) -> Self {
+        self.ping_url = Some(ping_url.into());
+        self
+    }
+
+    /// The root URL of the VariantValidator instance of the api_url, e.g. https://rest.variantvalidator.org
+    fn instance_url(&self) -> &str {
+        let api_url = self.api_url.trim_end_matches('/');
+        api_url
+            .strip_suffix("/VariantValidator/variantvalidator")
+            .unwrap_or(api_url)
+    }
+
+    fn tools_url(&self) -> String {
+        self.tools_url
+            .clone()
+            .unwrap_or_else(|| format!("{}/VariantValidator/tools/", self.instance_url()))
+    }
+
+    fn ping_url(&self) -> String {
+        self.ping_url.clone().unwrap_or_else(|| {
+            format!(
+                "{}/hello/?content-type=application%2Fjson",
+                self.instance_url()
+            )
+        })
+    }
+
+    /// Creates a client with the default settings, which will make its requests using the given reqwest Client.
+    ///
+    /// A reqwest Client holds a connection pool, so sharing a single Client between all HGVS and HGNC clients is recommended.
+    pub fn with_shared_client(client: Client) -> Self {
+        let rate_limiter = Ratelimiter::builder(2, Duration::from_secs(1))
+            .max_tokens(2)
+            .build()
+            .expect("Building rate limiter failed");
+        let api_url =
+            "https://rest.variantvalidator.org/VariantValidator/variantvalidator/".to_string();
+        HGVSClient::new(rate_limiter, 3, api_url, client, GenomeAssembly::Hg38)
+    }
+
+    /// Creates a client with the default settings, which requests variants on the given genome assembly.
+    pub fn default_for(genome_assembly: GenomeAssembly) -> Self {
+        HGVSClient::default().with_genome_assembly(genome_assembly)
+    }
+
+    /// Creates a client with the default settings, which requests variants on hg19 (GRCh37).
+    pub fn default_hg19() -> Self {
+        HGVSClient::default_for(GenomeAssembly::Hg19)
+    }
+
+    /// Changes the genome assembly on which variants are requested, keeping the client's rate limiter and reqwest Client.
+    pub fn with_genome_assembly(mut self, genome_assembly: GenomeAssembly) -> Self {
+        self.genome_assembly = genome_assembly;
+        self
+    }
+
+    /// If the client's genome assembly is missing from a VariantValidator response,
+    /// then use the response's selected assembly instead of returning an error.
+    pub fn with_selected_assembly_fallback(mut self, fall_back: bool) -> Self {
+        self.fall_back_to_selected_assembly = fall_back;
+        self
+    }
+
+    /// If strict, then a variant on an outdated transcript version is an error, even if VariantValidator accepted it.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// The URL which `request_and_validate_hgvs` would request for the HGVS string, without making the request.
+    pub fn fetch_url_for(&self, unvalidated_hgvs: &str) -> Result<String, HGVSError> {
+        let (transcript, allele) = Self::get_checked_transcript_and_allele(unvalidated_hgvs)?;
+        Ok(self.get_fetch_url(transcript, allele))
+    }
+
+    /// Sets whether chromosomes are given with or without a chr prefix, regardless of the assembly used by VariantValidator.
+    pub fn with_chr_naming(mut self, chr_naming: ChrNaming) -> Self {
+        self.chr_naming = chr_naming;
+        self
+    }
+
     pub fn get_fetch_url(&self, transcript: &str, allele: &str) -> String {
         format!(
             "{}/{}/{}%3A{}/{}?content-type=application%2Fjson",
@@ -77,40 +204,162 @@ impl HGVSClient {
         )
     }
 
-    fn fetch_request(
+    /// Checks that VariantValidator is reachable and accepts the client's requests (e.g. its extra headers).
+    pub fn ping(&self) -> Result<(), HGVSError> {
+        let ping_url = self.ping_url();
+        let unreachable = |problem: String| HGVSError::Unreachable {
+            url: ping_url.clone(),
+            problem,
+        };
+
+        let response = self
+            .get(&ping_url)
+            .send()
+            .map_err(|err| unreachable(err.to_string()))?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(unreachable(format!("status {}", response.status())))
+        }
+    }
+
+    fn get(&self, url: &str) -> RequestBuilder {
+        self.with_headers(self.client.get(url))
+    }
+
+    fn post(&self, url: &str, body: &Value) -> RequestBuilder {
+        self.with_headers(self.client.post(url).json(body))
+    }
+
+    fn with_headers(&self, request: RequestBuilder) -> RequestBuilder {
+        let mut request = request
+            .header("User-Agent", "PIVOT")
+            .header("Accept", "application/json");
+        for (name, value) in &self.extra_headers {
+            request = request.header(name, value);
+        }
+        request
+    }
+
+    fn fetch_request<R: DeserializeOwned>(
+        &self,
+        fetch_url: String,
+        unvalidated_hgvs: &str,
+    ) -> Result<R, HGVSError> {
+        self.fetch_request_with(fetch_url, unvalidated_hgvs, true, None)
+    }
+
+    /// If wait_for_rate_limit is false, a RateLimited error is returned instead of sleeping until a request may be made.
+    /// If a retry is rate limited, no more retries are made, and the error of the attempts already made is returned instead.
+    ///
+    /// If there is a retry_budget, each retry uses up one of its retries, and no more retries are made once it is used up.
+    fn fetch_request_with<R: DeserializeOwned>(
         &self,
         fetch_url: String,
         unvalidated_hgvs: &str,
-    ) -> Result<VariantValidatorResponse, HGVSError> {
-        for _ in 0..self.attempts {
-            if let Err(duration) = self.rate_limiter.try_wait() {
+        wait_for_rate_limit: bool,
+        retry_budget: Option<&Cell<usize>>,
+    ) -> Result<R, HGVSError> {
+        self.send_request_with(
+            || self.get(&fetch_url),
+            unvalidated_hgvs,
+            wait_for_rate_limit,
+            retry_budget,
+        )
+    }
+
+    fn post_request<R: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: &Value,
+        unvalidated_hgvs: &str,
+    ) -> Result<R, HGVSError> {
+        self.send_request_with(|| self.post(url, body), unvalidated_hgvs, true, None)
+    }
+
+    fn send_request_with<R: DeserializeOwned>(
+        &self,
+        request: impl Fn() -> RequestBuilder,
+        unvalidated_hgvs: &str,
+        wait_for_rate_limit: bool,
+        retry_budget: Option<&Cell<usize>>,
+    ) -> Result<R, HGVSError> {
+        let mut attempts_made = 0;
+        while attempts_made < self.attempts {
+            if attempts_made > 0
+                && let Some(retry_budget) = retry_budget
+            {
+                if retry_budget.get() == 0 {
+                    break;
+                }
+                retry_budget.set(retry_budget.get() - 1);
+            }
+
+            if let Some(rate_limiter) = &self.rate_limiter
+                && let Err(duration) = rate_limiter.try_wait()
+            {
+                if !wait_for_rate_limit {
+                    if attempts_made > 0 {
+                        break;
+                    }
+                    return Err(HGVSError::RateLimited {
+                        hgvs: unvalidated_hgvs.to_string(),
+                        retry_after: duration,
+                    });
+                }
                 sleep(duration);
             }
+            attempts_made += 1;
 
-            let response = self
-                .client
-                .get(fetch_url.clone())
-                .header("User-Agent", "PIVOT")
-                .header("Accept", "application/json")
-                .send()
-                .map_err(|err| HGVSError::FetchRequest {
-                    hgvs: unvalidated_hgvs.to_string(),
-                    err: err.to_string(),
-                })?;
+            let response = request().send().map_err(|err| HGVSError::FetchRequest {
+                hgvs: unvalidated_hgvs.to_string(),
+                err: err.to_string(),
+            })?;
 
             if response.status().is_success() {
-                return response.json::<VariantValidatorResponse>().map_err(|err| {
-                    HGVSError::DeserializeVariantValidatorResponseToSchema {
-                        hgvs: unvalidated_hgvs.to_string(),
-                        err: err.to_string(),
-                    }
-                });
+                return Self::parse_json_response(response, unvalidated_hgvs);
             }
         }
 
         Err(HGVSError::VariantValidatorAPI {
             hgvs: unvalidated_hgvs.to_string(),
-            attempts: self.attempts,
+            attempts: attempts_made,
+        })
+    }
+
+    /// When VariantValidator is down, it can respond with an HTML error page, which is reported as a NonJsonResponse
+    /// rather than as a problem with deserializing the JSON.
+    fn parse_json_response<R: DeserializeOwned>(
+        response: Response,
+        unvalidated_hgvs: &str,
+    ) -> Result<R, HGVSError> {
+        let status = response.status().as_u16();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map(str::to_string);
+        let body = response.text().map_err(|err| HGVSError::FetchRequest {
+            hgvs: unvalidated_hgvs.to_string(),
+            err: err.to_string(),
+        })?;
+
+        let is_json_content_type = content_type
+            .as_deref()
+            .is_none_or(|content_type| content_type.contains("json"));
+        if !is_json_content_type || body.trim_start().starts_with('<') {
+            return Err(HGVSError::NonJsonResponse {
+                hgvs: unvalidated_hgvs.to_string(),
+                status,
+                content_type: content_type.unwrap_or_default(),
+            });
+        }
+
+        serde_json::from_str(&body).map_err(|err| {
+            HGVSError::DeserializeVariantValidatorResponseToSchema {
+                hgvs: unvalidated_hgvs.to_string(),
+                err: err.to_string(),
+            }
         })
     }
 
@@ -118,7 +367,15 @@ impl HGVSClient {
         unvalidated_hgvs: &str,
         response: VariantValidatorResponse,
     ) -> Result<SingleVariantInfo, HGVSError> {
-        if response.flag == "warning" {
+        Ok(Self::get_response_for_valid_hgvs(unvalidated_hgvs, response)?.variant_info)
+    }
+
+    fn get_response_for_valid_hgvs(
+        unvalidated_hgvs: &str,
+        response: VariantValidatorResponse,
+    ) -> Result<SingleVariantResponse, HGVSError> {
+        let flag = response.response_flag();
+        if flag == ResponseFlag::Warning {
             let validation_warnings = response
                 .variant_info
                 .get("validation_warning_1")
@@ -130,166 +387,1746 @@ impl HGVSClient {
                 })?
                 .validation_warnings
                 .clone();
-            Err(HGVSError::InvalidHgvs {
-                hgvs: unvalidated_hgvs.to_string(),
+            Err(Self::invalid_hgvs_error(
+                unvalidated_hgvs,
+                validation_warnings,
+            ))
+        } else {
+            Self::check_flag(unvalidated_hgvs, &flag)?;
+            SingleVariantResponse::try_from(response)
+        }
+    }
+
+    /// A wrong reference base is given its own error, so that it can be corrected. Otherwise, all the warnings are returned.
+    fn invalid_hgvs_error(hgvs: &str, validation_warnings: Vec<String>) -> HGVSError {
+        let reference_base_mismatch = validation_warnings
+            .iter()
+            .find_map(|warning| ValidationWarning::new(warning.as_str()).reference_base_mismatch());
+        match reference_base_mismatch {
+            Some((submitted_ref, actual_ref)) => HGVSError::ReferenceBaseMismatch {
+                hgvs: hgvs.to_string(),
+                submitted_ref,
+                actual_ref,
+            },
+            None => HGVSError::InvalidHgvs {
+                hgvs: hgvs.to_string(),
                 problems: validation_warnings,
+            },
+        }
+    }
+
+    /// Checks that a response with this flag contains information on gene variants.
+    fn check_flag(hgvs: &str, flag: &ResponseFlag) -> Result<(), HGVSError> {
+        if *flag == ResponseFlag::Intergenic {
+            Err(HGVSError::IntergenicVariant {
+                hgvs: hgvs.to_string(),
             })
-        } else if !ALLOWED_FLAGS.contains(&response.flag.as_str()) {
+        } else if !ALLOWED_FLAGS.contains(flag) {
             Err(HGVSError::DisallowedFlag {
-                hgvs: unvalidated_hgvs.to_string(),
-                flag: response.flag.clone(),
-                allowed_flags: ALLOWED_FLAGS
-                    .to_vec()
-                    .iter()
-                    .map(|s| s.to_string())
-                    .collect(),
-            })
-        } else if !response.variant_info.len() == 1 {
-            Err(HGVSError::VariantValidatorResponseUnexpectedFormat {
-                hgvs: unvalidated_hgvs.to_string(),
-                format_issue:
-                    "VariantValidator response should contain information on exactly one variant."
-                        .to_string(),
+                hgvs: hgvs.to_string(),
+                flag: flag.to_string(),
+                allowed_flags: ALLOWED_FLAGS.iter().map(|f| f.to_string()).collect(),
             })
         } else {
-            Ok(response.variant_info.values().next().unwrap().clone())
+            Ok(())
         }
     }
 }
 
 impl HGVSData for HGVSClient {
     fn request_and_validate_hgvs(&self, unvalidated_hgvs: &str) -> Result<HgvsVariant, HGVSError> {
-        let (transcript, allele) = Self::get_transcript_and_allele(unvalidated_hgvs)?;
-        if !is_c_hgvs(allele) && !is_n_hgvs(allele) && !is_m_hgvs(allele) {
-            return Err(HGVSError::HgvsFormatNotAccepted {
-                hgvs: unvalidated_hgvs.to_string(),
-                problem: "Allele did not begin with c. or n. or m.".to_string(),
-            });
-        }
+        let variant_info = self.request_variant_info(unvalidated_hgvs)?;
+
+        self.create_hgvs_variant(unvalidated_hgvs, variant_info)
+    }
+
+    fn genome_assembly(&self) -> &GenomeAssembly {
+        &self.genome_assembly
+    }
+
+    fn chr_naming(&self) -> ChrNaming {
+        self.chr_naming
+    }
+
+    /// VariantValidator gives the loci of a variant on every assembly, so any assembly is supported.
+    fn get_vcf_coordinates(
+        &self,
+        unvalidated_hgvs: &str,
+        assembly: GenomeAssembly,
+    ) -> Result<(String, u32, String, String), HGVSError> {
+        let variant_info = self.request_variant_info(unvalidated_hgvs)?;
+        self.check_transcript_version(unvalidated_hgvs, &variant_info)?;
+        let hgvs_variant =
+            HgvsVariant::from_single_variant_info(&variant_info, unvalidated_hgvs, assembly)?
+                .with_chr_naming(self.chr_naming);
+        Ok(hgvs_variant.vcf_coordinates())
+    }
+}
+
+impl HGVSClient {
+    /// Like `request_and_validate_hgvs`, but also returns the advisory warnings which VariantValidator gave for the validated variant.
+    ///
+    /// The fatal problems which VariantValidator found with a variant that could not be validated are in the InvalidHgvs error instead.
+    pub fn request_and_validate_hgvs_with_diagnostics(
+        &self,
+        unvalidated_hgvs: &str,
+    ) -> Result<(HgvsVariant, Vec<ValidationWarning>), HGVSError> {
+        let hgvs_variant = self.request_and_validate_hgvs(unvalidated_hgvs)?;
+        let validation_warnings = hgvs_variant.validation_warnings();
+        Ok((hgvs_variant, validation_warnings))
+    }
+
+    /// Validates the HGVS string with a single request, and returns both the full response of VariantValidator
+    /// and the HgvsVariant abbreviated from it, using the locus on the given assembly.
+    pub fn get_both(
+        &self,
+        unvalidated_hgvs: &str,
+        assembly: GenomeAssembly,
+    ) -> Result<(SingleVariantResponse, HgvsVariant), HGVSError> {
+        let fetch_url = self.fetch_url_for(unvalidated_hgvs)?;
 
-        let fetch_url = self.get_fetch_url(transcript, allele);
+        let response = self.fetch_request(fetch_url, unvalidated_hgvs)?;
+        let response = Self::get_response_for_valid_hgvs(unvalidated_hgvs, response)?;
+        self.check_transcript_version(unvalidated_hgvs, &response.variant_info)?;
+
+        let hgvs_variant = HgvsVariant::from_single_variant_info(
+            &response.variant_info,
+            unvalidated_hgvs,
+            assembly,
+        )?
+        .with_chr_naming(self.chr_naming);
+        Ok((response, hgvs_variant))
+    }
 
-        let response = self.fetch_request(fetch_url.clone(), unvalidated_hgvs)?;
+    /// Like `request_and_validate_hgvs`, but returns a RateLimited error straight away if the rate limit has been reached,
+    /// instead of waiting until the request can be made.
+    pub fn try_request_and_validate_hgvs(
+        &self,
+        unvalidated_hgvs: &str,
+    ) -> Result<HgvsVariant, HGVSError> {
+        let fetch_url = self.fetch_url_for(unvalidated_hgvs)?;
 
+        let response = self.fetch_request_with(fetch_url, unvalidated_hgvs, false, None)?;
         let variant_info = Self::get_variant_info_for_valid_hgvs(unvalidated_hgvs, response)?;
 
-        let assemblies = variant_info.primary_assembly_loci;
+        self.create_hgvs_variant(unvalidated_hgvs, variant_info)
+    }
 
-        let assembly = assemblies
-            .get(&self.genome_assembly.to_string())
-            .ok_or_else(|| HGVSError::GenomeAssemblyNotFound {
-                hgvs: unvalidated_hgvs.to_string(),
-                desired_assembly: self.genome_assembly.to_string(),
-                found_assemblies: assemblies.keys().cloned().collect::<Vec<String>>(),
-            })?
-            .clone();
-
-        let position_string = assembly.vcf.pos;
-        let position = position_string.parse::<u32>().map_err(|_| {
-            HGVSError::InvalidVariantValidatorResponseElement {
-                hgvs: unvalidated_hgvs.to_string(),
-                element: position_string,
-                problem: "position should be parseable to u32".to_string(),
-            }
-        })?;
+    fn request_variant_info(&self, unvalidated_hgvs: &str) -> Result<SingleVariantInfo, HGVSError> {
+        let fetch_url = self.fetch_url_for(unvalidated_hgvs)?;
 
-        let p_hgvs = if variant_info
-            .hgvs_predicted_protein_consequence
-            .tlr
-            .is_empty()
-        {
-            None
-        } else {
-            Some(variant_info.hgvs_predicted_protein_consequence.tlr)
+        let response = self.fetch_request(fetch_url, unvalidated_hgvs)?;
+
+        Self::get_variant_info_for_valid_hgvs(unvalidated_hgvs, response)
+    }
+
+    /// Like `request_and_validate_hgvs`, but if VariantValidator warns that a more recent version of the transcript is available,
+    /// the variant is validated again on that version, and the returned HgvsVariant records the original HGVS in `upgraded_from`.
+    ///
+    /// The variant is upgraded at most once. If the upgraded variant can't be validated, an OutdatedTranscript error is returned.
+    pub fn request_and_validate_with_upgrade(
+        &self,
+        unvalidated_hgvs: &str,
+    ) -> Result<HgvsVariant, HGVSError> {
+        let variant_info = self.request_variant_info(unvalidated_hgvs)?;
+
+        let latest_transcript = variant_info
+            .validation_warnings
+            .iter()
+            .map(ValidationWarning::new)
+            .find_map(|warning| warning.latest_transcript().map(str::to_string));
+        let (current_transcript, allele) = get_transcript_and_allele(unvalidated_hgvs)?;
+        let Some(latest_transcript) =
+            latest_transcript.filter(|latest_transcript| latest_transcript != current_transcript)
+        else {
+            return self.create_hgvs_variant(unvalidated_hgvs, variant_info);
         };
 
-        let validated_hgvs = HgvsVariant::new(
-            self.genome_assembly.to_string(),
-            assembly.vcf.chr,
-            position,
-            assembly.vcf.reference,
-            assembly.vcf.alt,
-            variant_info.gene_symbol,
-            variant_info.gene_ids.hgnc_id,
-            transcript.to_string(),
-            allele.to_string(),
-            unvalidated_hgvs.to_string(),
-            assembly.hgvs_genomic_description,
-            p_hgvs,
-        );
-        Ok(validated_hgvs)
+        let upgraded_hgvs = format!("{latest_transcript}:{allele}");
+        self.request_and_validate_hgvs(&upgraded_hgvs)
+            .map(|hgvs_variant| hgvs_variant.with_upgraded_from(unvalidated_hgvs))
+            .map_err(|err| {
+                warn!(
+                    "Could not validate {upgraded_hgvs}, the upgrade of {unvalidated_hgvs}: {err}"
+                );
+                HGVSError::OutdatedTranscript {
+                    current: current_transcript.to_string(),
+                    latest: latest_transcript.clone(),
+                }
+            })
     }
-}
 
-impl HGVSClient {
-    fn get_transcript_and_allele(unvalidated_hgvs: &str) -> Result<(&str, &str), HGVSError> {
-        let split_hgvs = unvalidated_hgvs.split(':').collect::<Vec<&str>>();
-        let colon_count = split_hgvs.len() - 1;
-        if colon_count != 1 {
-            Err(HGVSError::HgvsFormatNotAccepted {
-                hgvs: unvalidated_hgvs.to_string(),
-                problem: "There must be exactly one colon in a HGVS string.".to_string(),
+    /// Validates each HGVS string with its own request, calling progress with (completed, total) after each one.
+    ///
+    /// The results are in the same order as the inputted HGVS strings.
+    pub fn request_and_validate_many_with_progress(
+        &self,
+        unvalidated_hgvs: &[&str],
+        mut progress: impl FnMut(usize, usize),
+    ) -> Vec<Result<HgvsVariant, HGVSError>> {
+        let total = unvalidated_hgvs.len();
+        unvalidated_hgvs
+            .iter()
+            .enumerate()
+            .map(|(index, hgvs)| {
+                let result = self.request_and_validate_hgvs(hgvs);
+                progress(index + 1, total);
+                result
             })
-        } else {
-            let transcript = split_hgvs[0];
-            let allele = split_hgvs[1];
-            Ok((transcript, allele))
+            .collect()
+    }
+
+    /// Validates each HGVS string with its own request, like `request_and_validate_many_with_progress`, but with a retry budget
+    /// shared by the whole batch instead of retrying each variant up to the client's number of attempts.
+    ///
+    /// Once the budget is used up and a variant fails, the remaining variants fail straight away with a VariantValidatorAPI error
+    /// without being requested. This bounds how long a batch takes while VariantValidator is unavailable.
+    pub fn request_and_validate_many_with_retry_budget(
+        &self,
+        unvalidated_hgvs: &[&str],
+        retry_budget: usize,
+    ) -> Vec<Result<HgvsVariant, HGVSError>> {
+        let retry_budget = Cell::new(retry_budget);
+        let mut budget_exhausted = false;
+        unvalidated_hgvs
+            .iter()
+            .map(|hgvs| {
+                if budget_exhausted {
+                    return Err(HGVSError::VariantValidatorAPI {
+                        hgvs: hgvs.to_string(),
+                        attempts: 0,
+                    });
+                }
+                let result = self.request_and_validate_hgvs_within_budget(hgvs, &retry_budget);
+                budget_exhausted = retry_budget.get() == 0
+                    && matches!(result, Err(HGVSError::VariantValidatorAPI { .. }));
+                result
+            })
+            .collect()
+    }
+
+    fn request_and_validate_hgvs_within_budget(
+        &self,
+        unvalidated_hgvs: &str,
+        retry_budget: &Cell<usize>,
+    ) -> Result<HgvsVariant, HGVSError> {
+        let fetch_url = self.fetch_url_for(unvalidated_hgvs)?;
+
+        let response =
+            self.fetch_request_with(fetch_url, unvalidated_hgvs, true, Some(retry_budget))?;
+        let variant_info = Self::get_variant_info_for_valid_hgvs(unvalidated_hgvs, response)?;
+
+        self.create_hgvs_variant(unvalidated_hgvs, variant_info)
+    }
+
+    /// Validates two HGVS strings which are believed to form a compound heterozygous pair, i.e. they must be in the same gene.
+    ///
+    /// If a gene (symbol or HGNC ID) is given, then both variants are also validated against it.
+    pub fn validate_compound_het(
+        &self,
+        hgvs1: &str,
+        hgvs2: &str,
+        gene: Option<&str>,
+    ) -> Result<(HgvsVariant, HgvsVariant), HGVSError> {
+        let variant1 = self.request_and_validate_hgvs(hgvs1)?;
+        let variant2 = self.request_and_validate_hgvs(hgvs2)?;
+
+        if let Some(gene) = gene {
+            variant1.validate_against_gene(gene)?;
+            variant2.validate_against_gene(gene)?;
+        }
+        if variant1.hgnc_id() != variant2.hgnc_id() {
+            return Err(HGVSError::CompoundHetGenesDiffer {
+                hgvs1: hgvs1.to_string(),
+                gene1: variant1.hgnc_id().to_string(),
+                hgvs2: hgvs2.to_string(),
+                gene2: variant2.hgnc_id().to_string(),
+            });
         }
+        Ok((variant1, variant2))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::hgvs::error::HGVSError;
-    use crate::hgvs::hgvs_client::HGVSClient;
-    use crate::hgvs::traits::HGVSData;
-    use rstest::{fixture, rstest};
+    /// Validates several HGVS strings with a single request to VariantValidator, which posts them all to its batch endpoint.
+    ///
+    /// The results are in the same order as the inputted HGVS strings. If a HGVS string is rejected
+    /// (either before the request is made, or by VariantValidator) then there will be an error at its position,
+    /// whereas an error with the request itself is returned as the outer error.
+    pub fn validate_batch(
+        &self,
+        unvalidated_hgvs: &[&str],
+    ) -> Result<Vec<Result<HgvsVariant, HGVSError>>, HGVSError> {
+        let variants_to_submit = unvalidated_hgvs
+            .iter()
+            .filter_map(|hgvs| Self::get_checked_transcript_and_allele(hgvs).ok())
+            .collect::<Vec<(&str, &str)>>();
 
-    // this forces tests to run sequentially
-    #[rstest]
-    fn hgvs_client_tests() {
-        let client = HGVSClient::default();
-        test_request_and_validate_hgvs_c_autosomal(&client);
-        test_request_and_validate_hgvs_c_x(&client);
-        test_request_and_validate_hgvs_n(&client);
-        test_request_and_validate_hgvs_m(&client);
-        test_request_and_validate_hgvs_wrong_reference_base_err(&client);
-        test_request_and_validate_hgvs_not_c_or_n_hgvs_err(&client);
+        if variants_to_submit.is_empty() {
+            return Ok(unvalidated_hgvs
+                .iter()
+                .filter_map(|hgvs| Self::get_checked_transcript_and_allele(hgvs).err())
+                .map(Err)
+                .collect());
+        }
+
+        let response = self.post_request(
+            self.api_url.trim_end_matches('/'),
+            &self.get_batch_body(&variants_to_submit),
+            &unvalidated_hgvs.join(", "),
+        )?;
+
+        self.get_batch_results(unvalidated_hgvs, response)
     }
 
-    fn test_request_and_validate_hgvs_c_autosomal(client: &HGVSClient) {
-        let unvalidated_hgvs = "NM_001173464.1:c.2860C>T";
-        let validated_hgvs = client.request_and_validate_hgvs(unvalidated_hgvs).unwrap();
-        assert_eq!(validated_hgvs.transcript_hgvs(), unvalidated_hgvs);
+    /// The body of a batch request, in which the variants and their transcripts are separated by "|".
+    fn get_batch_body(&self, variants: &[(&str, &str)]) -> Value {
+        let variant_descriptions = variants
+            .iter()
+            .map(|(transcript, allele)| format!("{}:{}", transcript, allele))
+            .collect::<Vec<String>>()
+            .join("|");
+        let transcripts = variants
+            .iter()
+            .map(|(transcript, _)| *transcript)
+            .collect::<Vec<&str>>()
+            .join("|");
+        serde_json::json!({
+            "genome_build": self.genome_assembly.to_string(),
+            "variant_description": variant_descriptions,
+            "select_transcripts": transcripts,
+        })
     }
 
-    fn test_request_and_validate_hgvs_c_x(client: &HGVSClient) {
-        let unvalidated_hgvs = "NM_000132.4:c.3637A>T";
-        let validated_hgvs = client.request_and_validate_hgvs(unvalidated_hgvs).unwrap();
-        assert_eq!(validated_hgvs.transcript_hgvs(), unvalidated_hgvs);
+    /// Validates a genomic HGVS string (e.g. NC_000012.12:g.39332405G>A) on the given assembly,
+    /// and returns an HgvsVariant for every transcript which the variant overlaps.
+    ///
+    /// MANE Select transcripts come first, and otherwise the variants are ordered by transcript.
+    pub fn validate_genomic(
+        &self,
+        unvalidated_g_hgvs: &str,
+        assembly: GenomeAssembly,
+    ) -> Result<Vec<HgvsVariant>, HGVSError> {
+        Self::check_ascii(unvalidated_g_hgvs)?;
+        let (reference, allele) = get_transcript_and_allele(unvalidated_g_hgvs)?;
+        if is_p_hgvs(allele) {
+            return Err(HGVSError::ProteinHgvsNotSupported {
+                hgvs: unvalidated_g_hgvs.to_string(),
+            });
+        }
+        if !is_g_hgvs(allele) {
+            return Err(HGVSError::HgvsFormatNotAccepted {
+                hgvs: unvalidated_g_hgvs.to_string(),
+                problem: "Allele did not begin with g.".to_string(),
+            });
+        }
+        let fetch_url = self.get_all_transcripts_fetch_url(assembly, reference, allele);
+
+        self.request_variants_on_all_transcripts(fetch_url, unvalidated_g_hgvs, assembly)
     }
 
-    fn test_request_and_validate_hgvs_n(client: &HGVSClient) {
-        let unvalidated_hgvs = "NR_002196.1:n.601G>T";
-        let validated_hgvs = client.request_and_validate_hgvs(unvalidated_hgvs).unwrap();
-        assert_eq!(validated_hgvs.transcript_hgvs(), unvalidated_hgvs);
+    /// Validates the HGVS string and returns an HgvsVariant for the variant on every transcript of the gene, rather than only the submitted transcript.
+    ///
+    /// MANE Select transcripts come first, and otherwise the variants are ordered by transcript.
+    pub fn request_all_transcripts(
+        &self,
+        unvalidated_hgvs: &str,
+    ) -> Result<Vec<HgvsVariant>, HGVSError> {
+        let (transcript, allele) = Self::get_checked_transcript_and_allele(unvalidated_hgvs)?;
+        let fetch_url =
+            self.get_all_transcripts_fetch_url(self.genome_assembly, transcript, allele);
+
+        self.request_variants_on_all_transcripts(fetch_url, unvalidated_hgvs, self.genome_assembly)
     }
 
-    fn test_request_and_validate_hgvs_m(client: &HGVSClient) {
-        let unvalidated_hgvs = "NC_012920.1:m.616T>C";
-        let validated_hgvs = client.request_and_validate_hgvs(unvalidated_hgvs).unwrap();
-        assert_eq!(validated_hgvs.transcript_hgvs(), unvalidated_hgvs);
+    /// The URL for a variant, with VariantValidator asked to project it onto all transcripts.
+    fn get_all_transcripts_fetch_url(
+        &self,
+        assembly: GenomeAssembly,
+        reference: &str,
+        allele: &str,
+    ) -> String {
+        format!(
+            "{}/{}/{}%3A{}/all?content-type=application%2Fjson",
+            self.api_url, assembly, reference, allele
+        )
     }
 
-    fn test_request_and_validate_hgvs_wrong_reference_base_err(client: &HGVSClient) {
-        let unvalidated_hgvs = "NM_001173464.1:c.2860G>T";
-        let result = client.request_and_validate_hgvs(unvalidated_hgvs);
-        assert!(matches!(result, Err(HGVSError::InvalidHgvs { .. })));
+    fn request_variants_on_all_transcripts(
+        &self,
+        fetch_url: String,
+        unvalidated_hgvs: &str,
+        assembly: GenomeAssembly,
+    ) -> Result<Vec<HgvsVariant>, HGVSError> {
+        let response: VariantValidatorResponse = self.fetch_request(fetch_url, unvalidated_hgvs)?;
+        let flag = response.response_flag();
+        if flag == ResponseFlag::Warning {
+            let validation_warnings = response
+                .variant_info
+                .into_values()
+                .flat_map(|variant_info| variant_info.validation_warnings)
+                .collect();
+            return Err(Self::invalid_hgvs_error(
+                unvalidated_hgvs,
+                validation_warnings,
+            ));
+        }
+        Self::check_flag(unvalidated_hgvs, &flag)?;
+
+        let mut variant_infos = response
+            .into_all_responses()
+            .into_iter()
+            .map(|response| response.variant_info)
+            .collect::<Vec<SingleVariantInfo>>();
+        variant_infos.sort_by_key(|variant_info| !variant_info.annotations.mane_select);
+
+        variant_infos
+            .into_iter()
+            .map(|variant_info| {
+                let transcript_hgvs = variant_info.hgvs_transcript_variant.clone();
+                self.create_hgvs_variant_on_assembly(&transcript_hgvs, variant_info, assembly)
+            })
+            .collect()
     }
 
-    fn test_request_and_validate_hgvs_not_c_or_n_hgvs_err(client: &HGVSClient) {
-        let unvalidated_hgvs = "NC_000012.12:g.39332405G>A";
-        let result = client.request_and_validate_hgvs(unvalidated_hgvs);
+    /// Lists the transcripts of a gene (given as a symbol or HGNC ID) known to VariantValidator.
+    pub fn gene_transcripts(&self, gene: &str) -> Result<Vec<TranscriptInfo>, HGVSError> {
+        let fetch_url = format!(
+            "{}gene2transcripts/{}?content-type=application%2Fjson",
+            self.tools_url(),
+            gene
+        );
+        let response = self.fetch_request(fetch_url, gene)?;
+        Self::get_transcript_infos(gene, response)
+    }
+
+    fn get_transcript_infos(
+        gene: &str,
+        response: Gene2TranscriptsResponse,
+    ) -> Result<Vec<TranscriptInfo>, HGVSError> {
+        if !response.error.is_empty() {
+            return Err(HGVSError::GeneTranscriptsNotFound {
+                gene: gene.to_string(),
+                problem: response.error,
+            });
+        }
+
+        Ok(response
+            .transcripts
+            .into_iter()
+            .map(|transcript| {
+                TranscriptInfo::new(
+                    transcript.reference,
+                    transcript.annotations.mane_select,
+                    transcript.length,
+                )
+            })
+            .collect())
+    }
+
+    /// Matches each of the inputted HGVS strings to its entry in a batch response via the submitted variant.
+    fn get_batch_results(
+        &self,
+        unvalidated_hgvs: &[&str],
+        response: VariantValidatorResponse,
+    ) -> Result<Vec<Result<HgvsVariant, HGVSError>>, HGVSError> {
+        let flag = response.response_flag();
+        if flag != ResponseFlag::Warning {
+            Self::check_flag(&unvalidated_hgvs.join(", "), &flag)?;
+        }
+
+        Ok(unvalidated_hgvs
+            .iter()
+            .map(|hgvs| {
+                Self::get_checked_transcript_and_allele(hgvs)?;
+
+                let (key, variant_info) = response
+                    .variant_info
+                    .iter()
+                    .find(|(_, variant_info)| variant_info.submitted_variant == *hgvs)
+                    .ok_or_else(|| HGVSError::VariantValidatorResponseUnexpectedFormat {
+                        hgvs: hgvs.to_string(),
+                        format_issue:
+                            "The batch response did not contain an entry for this variant."
+                                .to_string(),
+                    })?;
+
+                if key.starts_with("validation_warning") {
+                    Err(Self::invalid_hgvs_error(
+                        hgvs,
+                        variant_info.validation_warnings.clone(),
+                    ))
+                } else {
+                    self.create_hgvs_variant(hgvs, variant_info.clone())
+                }
+            })
+            .collect())
+    }
+
+    fn create_hgvs_variant(
+        &self,
+        unvalidated_hgvs: &str,
+        variant_info: SingleVariantInfo,
+    ) -> Result<HgvsVariant, HGVSError> {
+        self.create_hgvs_variant_on_assembly(unvalidated_hgvs, variant_info, self.genome_assembly)
+    }
+
+    /// Creates the HgvsVariant on the given assembly, after the same checks as every other variant validated by the client
+    /// (i.e. the transcript version check of strict mode, and the selected assembly fallback).
+    fn create_hgvs_variant_on_assembly(
+        &self,
+        unvalidated_hgvs: &str,
+        variant_info: SingleVariantInfo,
+        assembly: GenomeAssembly,
+    ) -> Result<HgvsVariant, HGVSError> {
+        self.check_transcript_version(unvalidated_hgvs, &variant_info)?;
+
+        let mut assembly_name = assembly.to_string();
+        if !variant_info
+            .primary_assembly_loci
+            .contains_key(&assembly_name)
+            && self.fall_back_to_selected_assembly
+        {
+            let selected_assembly = variant_info.selected_assembly.to_lowercase();
+            warn!(
+                "Genome assembly {} not found in VariantValidator response for {}. Falling back to selected assembly {}.",
+                assembly_name, unvalidated_hgvs, selected_assembly
+            );
+            assembly_name = selected_assembly;
+        }
+
+        Ok(HgvsVariant::from_single_variant_info_with_assembly_name(
+            &variant_info,
+            unvalidated_hgvs,
+            &assembly_name,
+        )?
+        .with_chr_naming(self.chr_naming))
+    }
+
+    fn check_transcript_version(
+        &self,
+        unvalidated_hgvs: &str,
+        variant_info: &SingleVariantInfo,
+    ) -> Result<(), HGVSError> {
+        if !self.strict {
+            return Ok(());
+        }
+
+        let outdated_transcript_warning = variant_info
+            .validation_warnings
+            .iter()
+            .map(ValidationWarning::new)
+            .find(ValidationWarning::is_transcript_version_warning);
+
+        if let Some(warning) = outdated_transcript_warning {
+            let (current, _) = get_transcript_and_allele(unvalidated_hgvs)?;
+            let latest = warning.latest_transcript().unwrap_or(warning.message());
+            return Err(HGVSError::OutdatedTranscript {
+                current: current.to_string(),
+                latest: latest.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl HGVSClient {
+    fn check_ascii(unvalidated_hgvs: &str) -> Result<(), HGVSError> {
+        if let Some((index, non_ascii_char)) =
+            unvalidated_hgvs.char_indices().find(|(_, c)| !c.is_ascii())
+        {
+            return Err(HGVSError::HgvsFormatNotAccepted {
+                hgvs: unvalidated_hgvs.to_string(),
+                problem: format!(
+                    "Non-ASCII character '{}' found at byte {}. Only ASCII characters are allowed",
+                    non_ascii_char, index
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn get_checked_transcript_and_allele(
+        unvalidated_hgvs: &str,
+    ) -> Result<(&str, &str), HGVSError> {
+        Self::check_ascii(unvalidated_hgvs)?;
+        let (transcript, allele) = get_transcript_and_allele(unvalidated_hgvs)?;
+        if is_p_hgvs(allele) {
+            return Err(HGVSError::ProteinHgvsNotSupported {
+                hgvs: unvalidated_hgvs.to_string(),
+            });
+        }
+        if !is_c_hgvs(allele) && !is_n_hgvs(allele) && !is_m_hgvs(allele) {
+            return Err(HGVSError::HgvsFormatNotAccepted {
+                hgvs: unvalidated_hgvs.to_string(),
+                problem: "Allele did not begin with c. or n. or m.".to_string(),
+            });
+        }
+        Ok((transcript, allele))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hgnc::HGNCClient;
+    use crate::hgvs::diagnostics::Diagnostics;
+    use crate::hgvs::enums::{ChrNaming, GenomeAssembly};
+    use crate::hgvs::error::HGVSError;
+    use crate::hgvs::hgvs_client::HGVSClient;
+    use crate::hgvs::hgvs_variant::HgvsVariant;
+    use crate::hgvs::json_schema::VariantValidatorResponse;
+    use crate::hgvs::test_utils::{load_fixture, load_response};
+    use crate::hgvs::traits::HGVSData;
+    use ratelimit::Ratelimiter;
+    use reqwest::blocking::Client;
+    use rstest::{fixture, rstest};
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    // this forces tests to run sequentially
+    #[rstest]
+    fn hgvs_client_tests() {
+        let client = HGVSClient::default();
+        test_request_and_validate_hgvs_c_autosomal(&client);
+        test_request_and_validate_hgvs_c_x(&client);
+        test_request_and_validate_hgvs_n(&client);
+        test_request_and_validate_hgvs_m(&client);
+        test_request_and_validate_hgvs_wrong_reference_base_err(&client);
+        test_request_and_validate_hgvs_not_c_or_n_hgvs_err(&client);
+    }
+
+    fn test_request_and_validate_hgvs_c_autosomal(client: &HGVSClient) {
+        let unvalidated_hgvs = "NM_001173464.1:c.2860C>T";
+        let validated_hgvs = client.request_and_validate_hgvs(unvalidated_hgvs).unwrap();
+        assert_eq!(validated_hgvs.transcript_hgvs(), unvalidated_hgvs);
+    }
+
+    fn test_request_and_validate_hgvs_c_x(client: &HGVSClient) {
+        let unvalidated_hgvs = "NM_000132.4:c.3637A>T";
+        let validated_hgvs = client.request_and_validate_hgvs(unvalidated_hgvs).unwrap();
+        assert_eq!(validated_hgvs.transcript_hgvs(), unvalidated_hgvs);
+    }
+
+    fn test_request_and_validate_hgvs_n(client: &HGVSClient) {
+        let unvalidated_hgvs = "NR_002196.1:n.601G>T";
+        let validated_hgvs = client.request_and_validate_hgvs(unvalidated_hgvs).unwrap();
+        assert_eq!(validated_hgvs.transcript_hgvs(), unvalidated_hgvs);
+    }
+
+    fn test_request_and_validate_hgvs_m(client: &HGVSClient) {
+        let unvalidated_hgvs = "NC_012920.1:m.616T>C";
+        let validated_hgvs = client.request_and_validate_hgvs(unvalidated_hgvs).unwrap();
+        assert_eq!(validated_hgvs.transcript_hgvs(), unvalidated_hgvs);
+    }
+
+    fn test_request_and_validate_hgvs_wrong_reference_base_err(client: &HGVSClient) {
+        let unvalidated_hgvs = "NM_001173464.1:c.2860G>T";
+        let result = client.request_and_validate_hgvs(unvalidated_hgvs);
+        assert!(matches!(
+            result,
+            Err(HGVSError::ReferenceBaseMismatch { .. })
+        ));
+    }
+
+    fn test_request_and_validate_hgvs_not_c_or_n_hgvs_err(client: &HGVSClient) {
+        let unvalidated_hgvs = "NC_000012.12:g.39332405G>A";
+        let result = client.request_and_validate_hgvs(unvalidated_hgvs);
+        assert!(matches!(
+            result,
+            Err(HGVSError::HgvsFormatNotAccepted { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_request_and_validate_hgvs_p_hgvs_err() {
+        let client = HGVSClient::default();
+        let result = client.request_and_validate_hgvs("NP_001166935.1:p.(Arg954Trp)");
+        assert!(matches!(
+            result,
+            Err(HGVSError::ProteinHgvsNotSupported { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_validate_batch() {
+        let mut server = mockito::Server::new();
+        let variant_validator = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "genome_build": "hg38",
+                "variant_description": "NM_001173464.1:c.2860C>T|NM_001173464.1:c.2860G>T",
+                "select_transcripts": "NM_001173464.1|NM_001173464.1"
+            })))
+            .with_body(load_fixture::<serde_json::Value>("kif21a_batch").to_string())
+            .expect(1)
+            .create();
+        let client = HGVSClient::default()
+            .with_api_url(format!("{}/", server.url()))
+            .without_rate_limit();
+
+        let results = client
+            .validate_batch(&[
+                "NM_001173464.1:c.2860C>T",
+                "NC_000012.12:g.39332405G>A",
+                "NM_001173464.1:c.2860G>T",
+            ])
+            .unwrap();
+
+        variant_validator.assert();
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[0].as_ref().unwrap().transcript_hgvs(),
+            "NM_001173464.1:c.2860C>T"
+        );
+        assert!(matches!(
+            results[1],
+            Err(HGVSError::HgvsFormatNotAccepted { .. })
+        ));
+        assert!(matches!(
+            results[2],
+            Err(HGVSError::ReferenceBaseMismatch { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_get_batch_results() {
+        let client = HGVSClient::default();
+        let response = load_response("kif21a_batch");
+
+        let results = client
+            .get_batch_results(
+                &[
+                    "NM_001173464.1:c.2860C>T",
+                    "NM_001173464.1:c.2860G>T",
+                    "NC_000012.12:g.39332405G>A",
+                ],
+                response,
+            )
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        let validated_hgvs = results[0].as_ref().unwrap();
+        assert_eq!(validated_hgvs.transcript_hgvs(), "NM_001173464.1:c.2860C>T");
+        assert_eq!(validated_hgvs.position(), 39332405);
+        assert!(matches!(
+            results[1],
+            Err(HGVSError::ReferenceBaseMismatch { .. })
+        ));
+        assert!(matches!(
+            results[2],
+            Err(HGVSError::HgvsFormatNotAccepted { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case("kif21a", "NM_001173464.1:c.2860C>T")]
+    #[case("mt_tl1_mitochondrial", "NC_012920.1:m.3243A>G")]
+    fn test_get_variant_info_allowed_flags(#[case] fixture: &str, #[case] hgvs: &str) {
+        let variant_info =
+            HGVSClient::get_variant_info_for_valid_hgvs(hgvs, load_response(fixture)).unwrap();
+        assert_eq!(variant_info.submitted_variant, hgvs);
+    }
+
+    #[rstest]
+    fn test_get_variant_info_intergenic_err() {
+        let result = HGVSClient::get_variant_info_for_valid_hgvs(
+            "NC_000012.12:g.39000000A>G",
+            load_response("intergenic"),
+        );
+        assert!(matches!(result, Err(HGVSError::IntergenicVariant { .. })));
+    }
+
+    #[rstest]
+    fn test_get_variant_info_warning_err() {
+        let result = HGVSClient::get_variant_info_for_valid_hgvs(
+            "NM_001173464.1:c.2860X>T",
+            load_response("kif21a_invalid_allele"),
+        );
+        assert!(matches!(result, Err(HGVSError::InvalidHgvs { .. })));
+    }
+
+    #[rstest]
+    fn test_get_variant_info_reference_base_mismatch_err() {
+        let result = HGVSClient::get_variant_info_for_valid_hgvs(
+            "NM_001173464.1:c.2860G>T",
+            load_response("kif21a_reference_mismatch"),
+        );
+        match result {
+            Err(HGVSError::ReferenceBaseMismatch {
+                submitted_ref,
+                actual_ref,
+                ..
+            }) => {
+                assert_eq!(submitted_ref, "G");
+                assert_eq!(actual_ref, "C");
+            }
+            _ => panic!("expected ReferenceBaseMismatch, got {result:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_get_variant_info_unknown_flag_err() {
+        let result = HGVSClient::get_variant_info_for_valid_hgvs(
+            "NC_012920.1:m.3243A>G",
+            load_response("empty_result"),
+        );
+        match result {
+            Err(HGVSError::DisallowedFlag { flag, .. }) => assert_eq!(flag, "empty_result"),
+            _ => panic!("expected DisallowedFlag, got {result:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_create_hgvs_variant_from_fixture() {
+        let client = HGVSClient::default();
+        let unvalidated_hgvs = "NM_001173464.1:c.2860C>T";
+        let response = load_response("kif21a");
+        let variant_info =
+            HGVSClient::get_variant_info_for_valid_hgvs(unvalidated_hgvs, response).unwrap();
+
+        let hgvs_variant = client
+            .create_hgvs_variant(unvalidated_hgvs, variant_info)
+            .unwrap();
+
+        let expected_hgvs_variant = HgvsVariant::new(
+            "hg38",
+            "chr12",
+            39332405,
+            "G",
+            "A",
+            "KIF21A",
+            "HGNC:19349",
+            "NM_001173464.1",
+            "c.2860C>T",
+            "NM_001173464.1:c.2860C>T",
+            "NC_000012.12:g.39332405G>A",
+            Some("NP_001166935.1:p.(Arg954Trp)"),
+        )
+        .with_transcript_description(
+            "Homo sapiens kinesin family member 21A (KIF21A), transcript variant 1, mRNA",
+        )
+        .with_ensembl_gene_id("ENSG00000139116")
+        .with_entrez_gene_id("55605")
+        .with_ccds_id("CCDS53776.1")
+        .with_ucsc_id("uc001rly.4")
+        .with_transcript_url("https://www.ncbi.nlm.nih.gov/nuccore/NM_001173464.1")
+        .with_submitted_variant(unvalidated_hgvs)
+        .with_normalized_hgvs(unvalidated_hgvs)
+        .with_selected_assembly("GRCh38");
+        assert_eq!(hgvs_variant, expected_hgvs_variant);
+    }
+
+    #[rstest]
+    fn test_request_and_validate_hgvs_with_diagnostics() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", mockito::Matcher::Any)
+            .with_body(
+                load_fixture::<serde_json::Value>("kif21a_transcript_version_warning").to_string(),
+            )
+            .create();
+        let client = HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit();
+        let unvalidated_hgvs = "NM_001173464.1:c.2860C>T";
+
+        let (hgvs_variant, validation_warnings) = client
+            .request_and_validate_hgvs_with_diagnostics(unvalidated_hgvs)
+            .unwrap();
+
+        assert_eq!(hgvs_variant.transcript_hgvs(), unvalidated_hgvs);
+        assert_eq!(validation_warnings.len(), 1);
+        assert_eq!(
+            validation_warnings[0].category(),
+            Some("TranscriptVersionWarning")
+        );
+        assert!(!hgvs_variant.diagnostics().is_fatal());
+        assert_eq!(hgvs_variant.diagnostics().warnings(), validation_warnings);
+    }
+
+    #[rstest]
+    fn test_request_and_validate_hgvs_with_diagnostics_fatal_err() {
+        let unvalidated_hgvs = "NM_001173464.1:c.2860X>T";
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", mockito::Matcher::Any)
+            .with_body(load_fixture::<serde_json::Value>("kif21a_invalid_allele").to_string())
+            .create();
+        let client = HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit();
+
+        let err = client
+            .request_and_validate_hgvs_with_diagnostics(unvalidated_hgvs)
+            .unwrap_err();
+
+        assert!(matches!(&err, HGVSError::InvalidHgvs { problems, .. } if problems.len() == 1));
+        let diagnostics = Diagnostics::from_error(&err);
+        assert!(diagnostics.is_fatal());
+        assert!(diagnostics.warnings().is_empty());
+    }
+
+    #[rstest]
+    fn test_create_hgvs_variant_strict() {
+        let unvalidated_hgvs = "NM_001173464.1:c.2860C>T";
+        let response = load_response("kif21a_transcript_version_warning");
+        let variant_info =
+            HGVSClient::get_variant_info_for_valid_hgvs(unvalidated_hgvs, response).unwrap();
+
+        let strict_client = HGVSClient::default().with_strict(true);
+        let result = strict_client.create_hgvs_variant(unvalidated_hgvs, variant_info.clone());
+        assert!(matches!(
+            result,
+            Err(HGVSError::OutdatedTranscript { current, latest })
+                if current == "NM_001173464.1" && latest == "NM_001173464.2"
+        ));
+
+        let lenient_client = HGVSClient::default();
+        lenient_client
+            .create_hgvs_variant(unvalidated_hgvs, variant_info)
+            .unwrap();
+    }
+
+    #[rstest]
+    fn test_create_hgvs_variant_selected_assembly_fallback() {
+        let unvalidated_hgvs = "NM_001173464.1:c.2860C>T";
+        let mut variant_info =
+            HGVSClient::get_variant_info_for_valid_hgvs(unvalidated_hgvs, load_response("kif21a"))
+                .unwrap();
+        variant_info.primary_assembly_loci.remove("hg38");
+
+        let strict_client = HGVSClient::default();
+        let result = strict_client.create_hgvs_variant(unvalidated_hgvs, variant_info.clone());
+        assert!(matches!(
+            result,
+            Err(HGVSError::GenomeAssemblyNotFound { .. })
+        ));
+
+        let fallback_client = HGVSClient::default().with_selected_assembly_fallback(true);
+        let hgvs_variant = fallback_client
+            .create_hgvs_variant(unvalidated_hgvs, variant_info)
+            .unwrap();
+        assert_eq!(hgvs_variant.assembly(), "hg38");
+        assert_eq!(hgvs_variant.chr(), "12");
+    }
+
+    #[rstest]
+    fn test_create_hgvs_variant_chr_naming() {
+        let unvalidated_hgvs = "NM_001173464.1:c.2860C>T";
+        let response = load_response("kif21a");
+        let variant_info =
+            HGVSClient::get_variant_info_for_valid_hgvs(unvalidated_hgvs, response).unwrap();
+
+        let bare_client = HGVSClient::default().with_chr_naming(ChrNaming::Bare);
+        let hgvs_variant = bare_client
+            .create_hgvs_variant(unvalidated_hgvs, variant_info.clone())
+            .unwrap();
+        assert_eq!(hgvs_variant.chr(), "12");
+
+        let mut grch38_variant_info = variant_info;
+        grch38_variant_info.primary_assembly_loci.remove("hg38");
+        let prefixed_client = HGVSClient::default()
+            .with_selected_assembly_fallback(true)
+            .with_chr_naming(ChrNaming::Prefixed);
+        let hgvs_variant = prefixed_client
+            .create_hgvs_variant(unvalidated_hgvs, grch38_variant_info)
+            .unwrap();
+        assert_eq!(hgvs_variant.assembly(), "hg38");
+        assert_eq!(hgvs_variant.chr(), "chr12");
+    }
+
+    #[rstest]
+    fn test_create_hgvs_variant_transcript_description() {
+        let unvalidated_hgvs = "NM_001173464.1:c.2860C>T";
+        let mut variant_info =
+            HGVSClient::get_variant_info_for_valid_hgvs(unvalidated_hgvs, load_response("kif21a"))
+                .unwrap();
+        let client = HGVSClient::default();
+
+        let hgvs_variant = client
+            .create_hgvs_variant(unvalidated_hgvs, variant_info.clone())
+            .unwrap();
+        assert_eq!(
+            hgvs_variant.transcript_description(),
+            Some("Homo sapiens kinesin family member 21A (KIF21A), transcript variant 1, mRNA")
+        );
+
+        variant_info.transcript_description = String::new();
+        let hgvs_variant = client
+            .create_hgvs_variant(unvalidated_hgvs, variant_info)
+            .unwrap();
+        assert_eq!(hgvs_variant.transcript_description(), None);
+    }
+
+    #[rstest]
+    #[case(GenomeAssembly::Hg38, ChrNaming::AsReturned, "chr12", 39332405)]
+    #[case(GenomeAssembly::Hg19, ChrNaming::AsReturned, "chr12", 39726207)]
+    #[case(GenomeAssembly::Hg38, ChrNaming::Bare, "12", 39332405)]
+    fn test_get_vcf_coordinates(
+        #[case] assembly: GenomeAssembly,
+        #[case] chr_naming: ChrNaming,
+        #[case] chr: &str,
+        #[case] position: u32,
+    ) {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", mockito::Matcher::Any)
+            .with_body(load_fixture::<serde_json::Value>("kif21a").to_string())
+            .create();
+        let rate_limiter = Ratelimiter::builder(2, Duration::from_secs(1))
+            .max_tokens(2)
+            .build()
+            .unwrap();
+        let client = HGVSClient::new(
+            rate_limiter,
+            1,
+            server.url(),
+            Client::new(),
+            GenomeAssembly::Hg38,
+        )
+        .with_chr_naming(chr_naming);
+
+        let vcf_coordinates = client
+            .get_vcf_coordinates("NM_001173464.1:c.2860C>T", assembly)
+            .unwrap();
+
+        assert_eq!(
+            vcf_coordinates,
+            (chr.to_string(), position, "G".to_string(), "A".to_string())
+        );
+    }
+
+    #[rstest]
+    fn test_request_and_validate_many_with_progress() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", mockito::Matcher::Any)
+            .with_body(load_fixture::<serde_json::Value>("kif21a").to_string())
+            .create();
+        let client = HGVSClient::default().with_api_url(server.url());
+        let mut progress_updates = vec![];
+
+        let results = client.request_and_validate_many_with_progress(
+            &[
+                "NM_001173464.1:c.2860C>T",
+                "NM_001173464.1:p.Arg954Trp",
+                "NM_001173464.1:c.2860C>T",
+            ],
+            |completed, total| progress_updates.push((completed, total)),
+        );
+
+        assert_eq!(progress_updates, vec![(1, 3), (2, 3), (3, 3)]);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(HGVSError::ProteinHgvsNotSupported { .. })
+        ));
+        assert!(results[2].is_ok());
+    }
+
+    /// A client whose VariantValidator returns the KIF21A fixture for c.2860C>T and c.2862G>A, and the same variant in another gene for c.2861G>A.
+    fn compound_het_client(server: &mut mockito::Server) -> HGVSClient {
+        let kif21a_response = load_fixture::<serde_json::Value>("kif21a");
+        let mut other_gene_response = kif21a_response.clone();
+        let variant_info = &mut other_gene_response["NM_001173464.1:c.2860C>T"];
+        variant_info["gene_symbol"] = json!("CLOCK");
+        variant_info["gene_ids"]["hgnc_id"] = json!("HGNC:2082");
+
+        server
+            .mock("GET", mockito::Matcher::Regex("c.286[02]".to_string()))
+            .with_body(kif21a_response.to_string())
+            .create();
+        server
+            .mock("GET", mockito::Matcher::Regex("c.2861G".to_string()))
+            .with_body(other_gene_response.to_string())
+            .create();
+        HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit()
+    }
+
+    #[rstest]
+    fn test_validate_compound_het() {
+        let mut server = mockito::Server::new();
+        let client = compound_het_client(&mut server);
+
+        let (variant1, variant2) = client
+            .validate_compound_het(
+                "NM_001173464.1:c.2860C>T",
+                "NM_001173464.1:c.2862G>A",
+                Some("KIF21A"),
+            )
+            .unwrap();
+
+        assert_eq!(variant1.hgnc_id(), "HGNC:19349");
+        assert_eq!(variant2.hgnc_id(), "HGNC:19349");
+    }
+
+    #[rstest]
+    fn test_validate_compound_het_different_genes_err() {
+        let mut server = mockito::Server::new();
+        let client = compound_het_client(&mut server);
+
+        let result = client.validate_compound_het(
+            "NM_001173464.1:c.2860C>T",
+            "NM_001173464.1:c.2861G>A",
+            None,
+        );
+
+        assert!(matches!(
+            result,
+            Err(HGVSError::CompoundHetGenesDiffer { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_request_and_validate_many_with_retry_budget() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_status(503)
+            .expect(3)
+            .create();
+        let client = HGVSClient::new(
+            Ratelimiter::builder(100, Duration::from_secs(1))
+                .max_tokens(100)
+                .initial_available(100)
+                .build()
+                .unwrap(),
+            3,
+            server.url(),
+            Client::new(),
+            GenomeAssembly::Hg38,
+        );
+        let unvalidated_hgvs = [
+            "NM_001173464.1:c.2860C>T",
+            "NM_001173464.1:c.2861G>A",
+            "NM_001173464.1:c.2862G>A",
+            "NM_001173464.1:c.2863G>A",
+        ];
+
+        let results = client.request_and_validate_many_with_retry_budget(&unvalidated_hgvs, 2);
+
+        assert!(matches!(
+            results[0],
+            Err(HGVSError::VariantValidatorAPI { attempts: 3, .. })
+        ));
+        for result in &results[1..] {
+            assert!(matches!(
+                result,
+                Err(HGVSError::VariantValidatorAPI { attempts: 0, .. })
+            ));
+        }
+        mock.assert();
+    }
+
+    #[rstest]
+    fn test_html_response() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", mockito::Matcher::Any)
+            .with_header("content-type", "text/html")
+            .with_body("<html><body><h1>503 Service Unavailable</h1></body></html>")
+            .create();
+        let client = HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit();
+
+        let result = client.request_and_validate_hgvs("NM_001173464.1:c.2860C>T");
+
+        match result {
+            Err(HGVSError::NonJsonResponse {
+                hgvs,
+                status,
+                content_type,
+            }) => {
+                assert_eq!(hgvs, "NM_001173464.1:c.2860C>T");
+                assert_eq!(status, 200);
+                assert_eq!(content_type, "text/html");
+            }
+            other => panic!("Expected NonJsonResponse, got {other:?}"),
+        }
+    }
+
+    fn upgrade_server(upgraded_status: usize) -> (mockito::ServerGuard, mockito::Mock) {
+        let mut server = mockito::Server::new();
+        server
+            .mock(
+                "GET",
+                mockito::Matcher::Regex(r"NM_001173464\.1".to_string()),
+            )
+            .with_body(
+                load_fixture::<serde_json::Value>("kif21a_transcript_version_warning").to_string(),
+            )
+            .create();
+        let upgraded_response = load_fixture::<serde_json::Value>("kif21a")
+            .to_string()
+            .replace("NM_001173464.1", "NM_001173464.2");
+        let upgraded_mock = server
+            .mock(
+                "GET",
+                mockito::Matcher::Regex(r"NM_001173464\.2".to_string()),
+            )
+            .with_status(upgraded_status)
+            .with_body(upgraded_response)
+            .expect_at_least(1)
+            .create();
+        (server, upgraded_mock)
+    }
+
+    #[rstest]
+    fn test_request_and_validate_with_upgrade() {
+        let (server, upgraded_mock) = upgrade_server(200);
+        let client = HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit();
+
+        let hgvs_variant = client
+            .request_and_validate_with_upgrade("NM_001173464.1:c.2860C>T")
+            .unwrap();
+
+        assert_eq!(hgvs_variant.transcript_hgvs(), "NM_001173464.2:c.2860C>T");
+        assert_eq!(
+            hgvs_variant.upgraded_from(),
+            Some("NM_001173464.1:c.2860C>T")
+        );
+        upgraded_mock.assert();
+    }
+
+    #[rstest]
+    fn test_request_and_validate_with_upgrade_fails() {
+        let (server, upgraded_mock) = upgrade_server(500);
+        let client = HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit();
+
+        let result = client.request_and_validate_with_upgrade("NM_001173464.1:c.2860C>T");
+
+        assert!(matches!(
+            result,
+            Err(HGVSError::OutdatedTranscript { current, latest })
+                if current == "NM_001173464.1" && latest == "NM_001173464.2"
+        ));
+        upgraded_mock.assert();
+    }
+
+    #[rstest]
+    fn test_get_both() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_body(load_fixture::<serde_json::Value>("kif21a").to_string())
+            .expect(1)
+            .create();
+        let client = HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit();
+
+        let (response, hgvs_variant) = client
+            .get_both("NM_001173464.1:c.2860C>T", GenomeAssembly::Hg19)
+            .unwrap();
+
+        assert_eq!(response.flag, "gene_variant");
+        assert_eq!(
+            response.variant_info.hgvs_transcript_variant,
+            hgvs_variant.transcript_hgvs()
+        );
+        assert_eq!(hgvs_variant.assembly(), "hg19");
+        assert_eq!(hgvs_variant.position(), 39726207);
+        mock.assert();
+    }
+
+    #[rstest]
+    fn test_try_request_and_validate_hgvs_rate_limited() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_body(load_fixture::<serde_json::Value>("kif21a").to_string())
+            .expect(2)
+            .create();
+        let rate_limiter = Ratelimiter::builder(2, Duration::from_secs(60))
+            .max_tokens(2)
+            .initial_available(2)
+            .build()
+            .unwrap();
+        let client = HGVSClient::new(
+            rate_limiter,
+            1,
+            server.url(),
+            Client::new(),
+            GenomeAssembly::Hg38,
+        );
+
+        for _ in 0..2 {
+            client
+                .try_request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+                .unwrap();
+        }
+        let result = client.try_request_and_validate_hgvs("NM_001173464.1:c.2860C>T");
+
+        match result {
+            Err(HGVSError::RateLimited { hgvs, retry_after }) => {
+                assert_eq!(hgvs, "NM_001173464.1:c.2860C>T");
+                assert!(retry_after <= Duration::from_secs(60));
+            }
+            other => panic!("Expected RateLimited, got {other:?}"),
+        }
+        mock.assert();
+    }
+
+    #[rstest]
+    fn test_try_request_and_validate_hgvs_rate_limited_retry() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_status(500)
+            .expect(1)
+            .create();
+        let rate_limiter = Ratelimiter::builder(1, Duration::from_secs(60))
+            .max_tokens(1)
+            .initial_available(1)
+            .build()
+            .unwrap();
+        let client = HGVSClient::new(
+            rate_limiter,
+            3,
+            server.url(),
+            Client::new(),
+            GenomeAssembly::Hg38,
+        );
+
+        let result = client.try_request_and_validate_hgvs("NM_001173464.1:c.2860C>T");
+
+        assert!(matches!(
+            result,
+            Err(HGVSError::VariantValidatorAPI { attempts: 1, .. })
+        ));
+        mock.assert();
+    }
+
+    #[rstest]
+    fn test_without_rate_limit() {
+        let mut server = mockito::Server::new();
+        let variant_validator = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_body(load_fixture::<serde_json::Value>("kif21a").to_string())
+            .expect(10)
+            .create();
+        let client = HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit();
+
+        let start = Instant::now();
+        for _ in 0..10 {
+            client
+                .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+                .unwrap();
+        }
+
+        // with the default rate limit of 2 requests per second, this would take several seconds
+        assert!(start.elapsed() < Duration::from_secs(2));
+        variant_validator.assert();
+    }
+
+    #[rstest]
+    fn test_ping() {
+        let mut server = mockito::Server::new();
+        server.mock("GET", "/hello/").with_status(200).create();
+        server.mock("GET", "/missing/").with_status(404).create();
+
+        let client = HGVSClient::default().with_ping_url(format!("{}/hello/", server.url()));
+        client.ping().unwrap();
+
+        let misconfigured_client =
+            HGVSClient::default().with_ping_url(format!("{}/missing/", server.url()));
+        assert!(matches!(
+            misconfigured_client.ping(),
+            Err(HGVSError::Unreachable { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("/VariantValidator/variantvalidator")]
+    #[case("/VariantValidator/variantvalidator/")]
+    fn test_ping_api_url(#[case] api_path: &str) {
+        let mut server = mockito::Server::new();
+        let hello = server
+            .mock("GET", "/hello/")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .create();
+        let client = HGVSClient::default().with_api_url(format!("{}{}", server.url(), api_path));
+
+        client.ping().unwrap();
+
+        hello.assert();
+    }
+
+    #[rstest]
+    fn test_tools_url_from_api_url() {
+        let client = HGVSClient::default();
+        assert_eq!(
+            client.tools_url(),
+            "https://rest.variantvalidator.org/VariantValidator/tools/"
+        );
+        assert_eq!(
+            client.ping_url(),
+            "https://rest.variantvalidator.org/hello/?content-type=application%2Fjson"
+        );
+
+        let local_client =
+            client.with_api_url("http://localhost:8000/VariantValidator/variantvalidator/");
+        assert_eq!(
+            local_client.tools_url(),
+            "http://localhost:8000/VariantValidator/tools/"
+        );
+    }
+
+    #[rstest]
+    fn test_fetch_url_for() {
+        let client = HGVSClient::default();
+
+        assert_eq!(
+            client.fetch_url_for("NM_001173464.1:c.2860C>T").unwrap(),
+            "https://rest.variantvalidator.org/VariantValidator/variantvalidator//hg38/NM_001173464.1%3Ac.2860C>T/NM_001173464.1?content-type=application%2Fjson"
+        );
+        assert!(matches!(
+            client.fetch_url_for("NM_001173464.1c.2860C>T"),
+            Err(HGVSError::HgvsFormatNotAccepted { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_with_genome_assembly() {
+        let client = HGVSClient::default().with_genome_assembly(GenomeAssembly::Hg19);
+
+        assert_eq!(client.genome_assembly(), &GenomeAssembly::Hg19);
+        assert_eq!(
+            client.get_fetch_url("NM_001173464.1", "c.2860C>T"),
+            "https://rest.variantvalidator.org/VariantValidator/variantvalidator//hg19/NM_001173464.1%3Ac.2860C>T/NM_001173464.1?content-type=application%2Fjson"
+        );
+    }
+
+    #[rstest]
+    fn test_with_shared_client() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        // a single connection is accepted, so every request must reuse it from the shared connection pool
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut request = BufReader::new(&stream);
+            let mut requested_paths = vec![];
+            for _ in 0..3 {
+                let mut line = String::new();
+                request.read_line(&mut line).unwrap();
+                requested_paths.push(line.split(' ').nth(1).unwrap().to_string());
+                // the request headers end with an empty line
+                while request.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                (&stream)
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+                    .unwrap();
+            }
+            requested_paths
+        });
+        let shared_client = Client::builder()
+            .timeout(Duration::from_secs(2))
+            .build()
+            .unwrap();
+        let api_url = format!("http://{address}/VariantValidator/variantvalidator/");
+        let hgvs_client =
+            HGVSClient::with_shared_client(shared_client.clone()).with_api_url(&api_url);
+        let other_hgvs_client =
+            HGVSClient::with_shared_client(shared_client.clone()).with_api_url(&api_url);
+        let hgnc_client = HGNCClient::with_shared_client(shared_client)
+            .with_api_url(format!("http://{address}/"));
+
+        hgvs_client.ping().unwrap();
+        other_hgvs_client.ping().unwrap();
+        hgnc_client.ping().unwrap();
+
+        assert_eq!(
+            server.join().unwrap(),
+            [
+                "/hello/?content-type=application%2Fjson",
+                "/hello/?content-type=application%2Fjson",
+                "/info"
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_request_and_validate_hgvs_non_ascii_err() {
+        let client = HGVSClient::default();
+        let result = client.request_and_validate_hgvs("NM_001173464.1:c.2860\u{2013}2A>G");
+        match result {
+            Err(HGVSError::HgvsFormatNotAccepted { problem, .. }) => {
+                assert!(problem.contains('\u{2013}'));
+            }
+            _ => panic!("Expected HgvsFormatNotAccepted error"),
+        }
+    }
+
+    #[rstest]
+    fn test_get_transcript_infos() {
+        let transcript_infos =
+            HGVSClient::get_transcript_infos("KIF21A", load_fixture("kif21a_gene2transcripts"))
+                .unwrap();
+
+        assert_eq!(transcript_infos.len(), 3);
+        let mane_select_transcripts = transcript_infos
+            .iter()
+            .filter(|transcript_info| transcript_info.is_mane_select())
+            .collect::<Vec<_>>();
+        assert_eq!(mane_select_transcripts.len(), 1);
+        assert_eq!(mane_select_transcripts[0].accession(), "NM_017641.4");
+        assert_eq!(mane_select_transcripts[0].length(), 6838);
+    }
+
+    #[rstest]
+    fn test_get_transcript_infos_err() {
+        let response = serde_json::from_value(json!({
+            "error": "Unable to recognise gene symbol KIF21",
+            "requested_symbol": "KIF21"
+        }))
+        .unwrap();
+
+        let result = HGVSClient::get_transcript_infos("KIF21", response);
+
+        assert!(matches!(
+            result,
+            Err(HGVSError::GeneTranscriptsNotFound { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_extra_headers_are_sent() {
+        let mut server = mockito::Server::new();
+        let gene2transcripts = server
+            .mock("GET", "/gene2transcripts/KIF21A")
+            .match_query(mockito::Matcher::Any)
+            .match_header("Authorization", "Bearer secret-token")
+            .match_header("User-Agent", "PIVOT")
+            .with_body(load_fixture::<serde_json::Value>("kif21a_gene2transcripts").to_string())
+            .create();
+        let client = HGVSClient::default()
+            .with_tools_url(format!("{}/", server.url()))
+            .with_extra_headers(HashMap::from([(
+                "Authorization".to_string(),
+                "Bearer secret-token".to_string(),
+            )]));
+
+        let transcripts = client.gene_transcripts("KIF21A").unwrap();
+
+        gene2transcripts.assert();
+        assert_eq!(transcripts.len(), 3);
+    }
+
+    #[rstest]
+    fn test_default_hg19() {
+        let client = HGVSClient::default_hg19();
+
+        assert_eq!(client.genome_assembly(), &GenomeAssembly::Hg19);
+    }
+
+    #[rstest]
+    #[case(GenomeAssembly::Hg38)]
+    #[case(GenomeAssembly::Hg19)]
+    fn test_default_for(#[case] assembly: GenomeAssembly) {
+        let client = HGVSClient::default_for(assembly);
+
+        assert_eq!(client.genome_assembly(), &assembly);
+    }
+
+    #[rstest]
+    fn test_validate_genomic() {
+        let mut response = load_fixture::<serde_json::Value>("kif21a");
+        let mut mane_select_info = response["NM_001173464.1:c.2860C>T"].clone();
+        mane_select_info["hgvs_transcript_variant"] = json!("NM_017641.4:c.2959C>T");
+        mane_select_info["annotations"]["mane_select"] = json!(true);
+        response["NM_017641.4:c.2959C>T"] = mane_select_info;
+
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock(
+                "GET",
+                mockito::Matcher::Regex(
+                    "^/hg38/NC_000012.12%3Ag.39332405G(>|%3E)A/all".to_string(),
+                ),
+            )
+            .with_body(response.to_string())
+            .create();
+        let client = HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit();
+
+        let hgvs_variants = client
+            .validate_genomic("NC_000012.12:g.39332405G>A", GenomeAssembly::Hg38)
+            .unwrap();
+
+        mock.assert();
+        let transcripts = hgvs_variants
+            .iter()
+            .map(HgvsVariant::transcript)
+            .collect::<Vec<&str>>();
+        assert_eq!(transcripts, vec!["NM_017641.4", "NM_001173464.1"]);
+        assert!(
+            hgvs_variants
+                .iter()
+                .all(|hgvs_variant| hgvs_variant.position() == 39332405)
+        );
+    }
+
+    #[rstest]
+    fn test_request_all_transcripts() {
+        let response = load_fixture::<serde_json::Value>("kif21a_all_transcripts");
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock(
+                "GET",
+                mockito::Matcher::Regex("^/hg38/NM_001173464.1%3Ac.2860C(>|%3E)T/all".to_string()),
+            )
+            .with_body(response.to_string())
+            .create();
+        let client = HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit();
+
+        let hgvs_variants = client
+            .request_all_transcripts("NM_001173464.1:c.2860C>T")
+            .unwrap();
+
+        mock.assert();
+        let transcript_hgvs = hgvs_variants
+            .iter()
+            .map(HgvsVariant::transcript_hgvs)
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            transcript_hgvs,
+            vec![
+                "NM_001173464.2:c.2860C>T",
+                "NM_001173464.1:c.2860C>T",
+                "NM_017641.4:c.2779C>T"
+            ]
+        );
+        assert!(
+            hgvs_variants
+                .iter()
+                .all(|hgvs_variant| hgvs_variant.g_hgvs() == "NC_000012.12:g.39332405G>A")
+        );
+    }
+
+    #[rstest]
+    fn test_request_all_transcripts_strict_outdated_transcript_err() {
+        let response = load_fixture::<serde_json::Value>("kif21a_all_transcripts");
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", mockito::Matcher::Any)
+            .with_body(response.to_string())
+            .create();
+        let client = HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit()
+            .with_strict(true);
+
+        let result = client.request_all_transcripts("NM_001173464.1:c.2860C>T");
+
+        assert!(matches!(
+            result,
+            Err(HGVSError::OutdatedTranscript { current, latest })
+                if current == "NM_001173464.1" && latest == "NM_001173464.2"
+        ));
+    }
+
+    #[rstest]
+    fn test_request_all_transcripts_selected_assembly_fallback() {
+        let mut response = load_fixture::<serde_json::Value>("kif21a_all_transcripts");
+        for (_, variant_info) in response.as_object_mut().unwrap().iter_mut() {
+            if let Some(loci) = variant_info
+                .get_mut("primary_assembly_loci")
+                .and_then(serde_json::Value::as_object_mut)
+            {
+                loci.remove("hg38");
+            }
+        }
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", mockito::Matcher::Any)
+            .with_body(response.to_string())
+            .create();
+        let client = HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit()
+            .with_selected_assembly_fallback(true);
+
+        let hgvs_variants = client
+            .request_all_transcripts("NM_001173464.1:c.2860C>T")
+            .unwrap();
+
+        assert_eq!(hgvs_variants.len(), 3);
+        assert!(
+            hgvs_variants
+                .iter()
+                .all(|hgvs_variant| hgvs_variant.assembly() == "hg38")
+        );
+    }
+
+    #[rstest]
+    fn test_validate_genomic_strict_outdated_transcript_err() {
+        let response = load_fixture::<serde_json::Value>("kif21a_all_transcripts");
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", mockito::Matcher::Any)
+            .with_body(response.to_string())
+            .create();
+        let client = HGVSClient::default()
+            .with_api_url(server.url())
+            .without_rate_limit()
+            .with_strict(true);
+
+        let result = client.validate_genomic("NC_000012.12:g.39332405G>A", GenomeAssembly::Hg38);
+
+        assert!(matches!(
+            result,
+            Err(HGVSError::OutdatedTranscript { current, .. }) if current == "NM_001173464.1"
+        ));
+    }
+
+    #[rstest]
+    fn test_validate_genomic_non_ascii_err() {
+        let client = HGVSClient::default();
+
+        let result =
+            client.validate_genomic("NC_000012.12:g.39332405G\u{2013}A", GenomeAssembly::Hg38);
+
+        assert!(matches!(
+            result,
+            Err(HGVSError::HgvsFormatNotAccepted { problem, .. }) if problem.contains('\u{2013}')
+        ));
+    }
+
+    #[rstest]
+    fn test_validate_genomic_p_hgvs_err() {
+        let client = HGVSClient::default();
+
+        let result = client.validate_genomic("NP_001166935.1:p.(Arg954Trp)", GenomeAssembly::Hg38);
+
+        assert!(matches!(
+            result,
+            Err(HGVSError::ProteinHgvsNotSupported { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_validate_genomic_not_g_hgvs_err() {
+        let client = HGVSClient::default();
+
+        let result = client.validate_genomic("NM_001173464.1:c.2860C>T", GenomeAssembly::Hg38);
+
         assert!(matches!(
             result,
             Err(HGVSError::HgvsFormatNotAccepted { .. })
@@ -1,7 +1,14 @@
 #![allow(unused)]
-use crate::hgvs::enums::{AlleleCount, ChromosomalSex};
+use crate::hgvs::diagnostics::Diagnostics;
+use crate::hgvs::enums::{
+    AlleleCount, ChrNaming, ChromosomalSex, GenomeAssembly, HgvsSyntax, VariantClass,
+};
 use crate::hgvs::error::HGVSError;
-use crate::hgvs::utils::{is_c_hgvs, is_m_hgvs, is_n_hgvs};
+use crate::hgvs::genomic_locus::GenomicLocus;
+use crate::hgvs::json_schema::SingleVariantInfo;
+use crate::hgvs::transcript_selection::TranscriptSelection;
+use crate::hgvs::utils::{get_transcript_and_allele, is_c_hgvs, is_n_hgvs, non_empty};
+use crate::hgvs::validation_warning::ValidationWarning;
 use crate::utils::is_hgnc_id;
 use phenopackets::ga4gh::vrsatile::v1::{
     Expression, GeneDescriptor, MoleculeContext, VariationDescriptor, VcfRecord,
@@ -9,9 +16,15 @@ use phenopackets::ga4gh::vrsatile::v1::{
 use phenopackets::schema::v2::core::{
     AcmgPathogenicityClassification, OntologyClass, TherapeuticActionability, VariantInterpretation,
 };
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
 use uuid::Uuid;
 
+/// A position with an offset from an exon boundary, e.g., 123+4 or -45-2
+static INTRONIC_OFFSET_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(^|[_(])[-*]?\d+[+-]\d+").unwrap());
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct HgvsVariant {
@@ -39,6 +52,42 @@ pub struct HgvsVariant {
     g_hgvs: String,
     /// Protein level HGVS, if available
     p_hgvs: Option<String>,
+    /// Transcript description, e.g., Homo sapiens kinesin family member 21A (KIF21A), transcript variant 1, mRNA
+    #[serde(default)]
+    transcript_description: Option<String>,
+    /// Ensembl gene identifier, e.g., ENSG00000139116
+    #[serde(default)]
+    ensembl_gene_id: Option<String>,
+    /// Entrez (NCBI) gene identifier, e.g., 55605
+    #[serde(default)]
+    entrez_gene_id: Option<String>,
+    /// Consensus CDS identifier of the transcript, e.g., CCDS53776.1
+    #[serde(default)]
+    ccds_id: Option<String>,
+    /// UCSC identifier, e.g., uc001rly.4
+    #[serde(default)]
+    ucsc_id: Option<String>,
+    /// NCBI URL of the transcript, e.g., https://www.ncbi.nlm.nih.gov/nuccore/NM_001173464.1
+    #[serde(default)]
+    transcript_url: Option<String>,
+    /// The variant as submitted to VariantValidator, e.g., NM_001173464:c.2860C>T
+    #[serde(default)]
+    submitted_variant: Option<String>,
+    /// The variant as normalized by VariantValidator, e.g., NM_001173464.1:c.2860C>T
+    #[serde(default)]
+    normalized_hgvs: Option<String>,
+    /// The assembly which VariantValidator selected, e.g., GRCh38
+    #[serde(default)]
+    selected_assembly: Option<String>,
+    /// Which transcript selection sets (e.g. MANE Select) the transcript belongs to
+    #[serde(default)]
+    transcript_selection: TranscriptSelection,
+    /// The advisory warnings which VariantValidator gave when validating the variant
+    #[serde(default)]
+    validation_warnings: Vec<String>,
+    /// The HGVS on an outdated transcript version which was upgraded to this variant, e.g., NM_001173464.1:c.2860C>T
+    #[serde(default)]
+    upgraded_from: Option<String>,
 }
 
 impl HgvsVariant {
@@ -70,13 +119,231 @@ impl HgvsVariant {
             transcript_hgvs: transcript_hgvs.into(),
             g_hgvs: g_hgvs.into(),
             p_hgvs: p_hgvs.map(|s| s.into()),
+            transcript_description: None,
+            ensembl_gene_id: None,
+            entrez_gene_id: None,
+            ccds_id: None,
+            ucsc_id: None,
+            transcript_url: None,
+            submitted_variant: None,
+            normalized_hgvs: None,
+            selected_assembly: None,
+            transcript_selection: TranscriptSelection::default(),
+            validation_warnings: vec![],
+            upgraded_from: None,
         }
     }
 
+    /// As new, but the assembly is given as a GenomeAssembly, which is stored by its name, e.g., hg38.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_typed(
+        assembly: GenomeAssembly,
+        chr: impl Into<String>,
+        position: u32,
+        ref_allele: impl Into<String>,
+        alt_allele: impl Into<String>,
+        symbol: impl Into<String>,
+        hgnc_id: impl Into<String>,
+        transcript: impl Into<String>,
+        allele: impl Into<String>,
+        transcript_hgvs: impl Into<String>,
+        g_hgvs: impl Into<String>,
+        p_hgvs: Option<impl Into<String>>,
+    ) -> Self {
+        HgvsVariant::new(
+            assembly.to_string(),
+            chr,
+            position,
+            ref_allele,
+            alt_allele,
+            symbol,
+            hgnc_id,
+            transcript,
+            allele,
+            transcript_hgvs,
+            g_hgvs,
+            p_hgvs,
+        )
+    }
+
+    /// Constructs a HgvsVariant from VariantValidator's information on the variant transcript_hgvs, using its locus on the given assembly.
+    pub fn from_single_variant_info(
+        info: &SingleVariantInfo,
+        transcript_hgvs: &str,
+        assembly: GenomeAssembly,
+    ) -> Result<Self, HGVSError> {
+        Self::from_single_variant_info_with_assembly_name(
+            info,
+            transcript_hgvs,
+            &assembly.to_string(),
+        )
+    }
+
+    /// As from_single_variant_info, but the assembly can be any key of primary_assembly_loci (e.g. grch38).
+    ///
+    /// The assembly of the HgvsVariant is the name of the GenomeAssembly (e.g. hg38 for grch38), if there is one,
+    /// so that it matches the assembly of variants created from the other key.
+    pub(crate) fn from_single_variant_info_with_assembly_name(
+        info: &SingleVariantInfo,
+        transcript_hgvs: &str,
+        assembly_name: &str,
+    ) -> Result<Self, HGVSError> {
+        let (transcript, allele) = get_transcript_and_allele(transcript_hgvs)?;
+
+        let assembly = info
+            .primary_assembly_loci
+            .get(assembly_name)
+            .ok_or_else(|| HGVSError::GenomeAssemblyNotFound {
+                hgvs: transcript_hgvs.to_string(),
+                desired_assembly: assembly_name.to_string(),
+                found_assemblies: info
+                    .primary_assembly_loci
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<String>>(),
+            })?;
+
+        let position_string = &assembly.vcf.pos;
+        let position = position_string.parse::<u32>().map_err(|_| {
+            HGVSError::InvalidVariantValidatorResponseElement {
+                hgvs: transcript_hgvs.to_string(),
+                element: position_string.clone(),
+                problem: "position should be parseable to u32".to_string(),
+            }
+        })?;
+
+        let p_hgvs = non_empty(info.hgvs_predicted_protein_consequence.tlr.clone());
+
+        let canonical_assembly_name = assembly_name
+            .parse::<GenomeAssembly>()
+            .map(|genome_assembly| genome_assembly.to_string())
+            .unwrap_or_else(|_| assembly_name.to_string());
+
+        let mut hgvs_variant = HgvsVariant::new(
+            canonical_assembly_name,
+            assembly.vcf.chr.clone(),
+            position,
+            assembly.vcf.reference.clone(),
+            assembly.vcf.alt.clone(),
+            info.gene_symbol.clone(),
+            info.gene_ids.hgnc_id.clone(),
+            transcript,
+            allele,
+            transcript_hgvs,
+            assembly.hgvs_genomic_description.clone(),
+            p_hgvs,
+        );
+        if let Some(transcript_description) = non_empty(info.transcript_description.clone()) {
+            hgvs_variant = hgvs_variant.with_transcript_description(transcript_description);
+        }
+        if let Some(ensembl_gene_id) = non_empty(info.gene_ids.ensembl_gene_id.clone()) {
+            hgvs_variant = hgvs_variant.with_ensembl_gene_id(ensembl_gene_id);
+        }
+        if let Some(entrez_gene_id) = non_empty(info.gene_ids.entrez_gene_id.clone()) {
+            hgvs_variant = hgvs_variant.with_entrez_gene_id(entrez_gene_id);
+        }
+        if let Some(ccds_id) = info.annotations.db_xref.ccds.clone().and_then(non_empty) {
+            hgvs_variant = hgvs_variant.with_ccds_id(ccds_id);
+        }
+        if let Some(ucsc_id) = non_empty(info.gene_ids.ucsc_id.clone()) {
+            hgvs_variant = hgvs_variant.with_ucsc_id(ucsc_id);
+        }
+        if let Some(transcript_url) = info.transcript_url() {
+            hgvs_variant = hgvs_variant.with_transcript_url(transcript_url);
+        }
+        hgvs_variant = hgvs_variant.with_validation_warnings(info.validation_warnings.clone());
+        if let Some(submitted_variant) = non_empty(info.submitted_variant.clone()) {
+            hgvs_variant = hgvs_variant.with_submitted_variant(submitted_variant);
+        }
+        if let Some(normalized_hgvs) = non_empty(info.hgvs_transcript_variant.clone()) {
+            hgvs_variant = hgvs_variant.with_normalized_hgvs(normalized_hgvs);
+        }
+        if let Some(selected_assembly) = non_empty(info.selected_assembly.clone()) {
+            hgvs_variant = hgvs_variant.with_selected_assembly(selected_assembly);
+        }
+        hgvs_variant =
+            hgvs_variant.with_transcript_selection(TranscriptSelection::from(&info.annotations));
+        Ok(hgvs_variant)
+    }
+
+    pub fn with_transcript_description(
+        mut self,
+        transcript_description: impl Into<String>,
+    ) -> Self {
+        self.transcript_description = Some(transcript_description.into());
+        self
+    }
+
+    pub fn with_ensembl_gene_id(mut self, ensembl_gene_id: impl Into<String>) -> Self {
+        self.ensembl_gene_id = Some(ensembl_gene_id.into());
+        self
+    }
+
+    pub fn with_entrez_gene_id(mut self, entrez_gene_id: impl Into<String>) -> Self {
+        self.entrez_gene_id = Some(entrez_gene_id.into());
+        self
+    }
+
+    pub fn with_ccds_id(mut self, ccds_id: impl Into<String>) -> Self {
+        self.ccds_id = Some(ccds_id.into());
+        self
+    }
+
+    pub fn with_ucsc_id(mut self, ucsc_id: impl Into<String>) -> Self {
+        self.ucsc_id = Some(ucsc_id.into());
+        self
+    }
+
+    pub fn with_transcript_url(mut self, transcript_url: impl Into<String>) -> Self {
+        self.transcript_url = Some(transcript_url.into());
+        self
+    }
+
+    /// Renames the chromosome, e.g. from 12 to chr12.
+    pub fn with_chr_naming(mut self, chr_naming: ChrNaming) -> Self {
+        self.chr = chr_naming.apply(&self.chr);
+        self
+    }
+
+    pub fn with_submitted_variant(mut self, submitted_variant: impl Into<String>) -> Self {
+        self.submitted_variant = Some(submitted_variant.into());
+        self
+    }
+
+    pub fn with_normalized_hgvs(mut self, normalized_hgvs: impl Into<String>) -> Self {
+        self.normalized_hgvs = Some(normalized_hgvs.into());
+        self
+    }
+
+    pub fn with_selected_assembly(mut self, selected_assembly: impl Into<String>) -> Self {
+        self.selected_assembly = Some(selected_assembly.into());
+        self
+    }
+
+    pub fn with_validation_warnings(mut self, validation_warnings: Vec<String>) -> Self {
+        self.validation_warnings = validation_warnings;
+        self
+    }
+
+    pub fn with_upgraded_from(mut self, upgraded_from: impl Into<String>) -> Self {
+        self.upgraded_from = Some(upgraded_from.into());
+        self
+    }
+
+    pub fn with_transcript_selection(mut self, transcript_selection: TranscriptSelection) -> Self {
+        self.transcript_selection = transcript_selection;
+        self
+    }
+
     pub fn assembly(&self) -> &str {
         self.assembly.as_ref()
     }
 
+    /// The assembly as a GenomeAssembly, or None if it is not a supported assembly.
+    pub fn assembly_enum(&self) -> Option<GenomeAssembly> {
+        self.assembly.parse().ok()
+    }
+
     pub fn chr(&self) -> &str {
         self.chr.as_ref()
     }
@@ -85,6 +352,27 @@ impl HgvsVariant {
         self.position
     }
 
+    /// The (start, end) of the reference bases affected by the variant, with both ends inclusive.
+    ///
+    /// The anchor base which VCF gives for indels (e.g. TGA>T) is not part of the span.
+    /// Insertions affect no reference bases, so the span of an insertion is the two bases on either side of it.
+    pub fn genomic_span(&self) -> (u32, u32) {
+        let has_anchor_base = self.ref_allele.len() != self.alt_allele.len()
+            && !self.ref_allele.is_empty()
+            && self.ref_allele.get(..1) == self.alt_allele.get(..1);
+        let (start, ref_length) = if has_anchor_base {
+            (self.position + 1, self.ref_allele.len() as u32 - 1)
+        } else {
+            (self.position, self.ref_allele.len() as u32)
+        };
+
+        if ref_length == 0 {
+            (start.saturating_sub(1), start)
+        } else {
+            (start, start + ref_length - 1)
+        }
+    }
+
     pub fn ref_allele(&self) -> &str {
         self.ref_allele.as_ref()
     }
@@ -113,6 +401,11 @@ impl HgvsVariant {
         self.transcript_hgvs.as_ref()
     }
 
+    /// The key under which a HgvsVariant is cached, so that variants on different assemblies are cached separately.
+    pub(crate) fn cache_key(assembly: &str, transcript_hgvs: &str) -> String {
+        format!("{assembly}|{transcript_hgvs}")
+    }
+
     pub fn g_hgvs(&self) -> &str {
         self.g_hgvs.as_ref()
     }
@@ -121,6 +414,155 @@ impl HgvsVariant {
         self.p_hgvs.as_ref().map(|phgvs| phgvs.to_string())
     }
 
+    pub fn transcript_description(&self) -> Option<&str> {
+        self.transcript_description.as_deref()
+    }
+
+    /// The chromosome and position of the variant on its assembly, which can be used to sort variants.
+    pub fn genomic_locus(&self) -> Result<GenomicLocus, HGVSError> {
+        Ok(GenomicLocus::new(
+            self.assembly.parse()?,
+            self.chr.clone(),
+            self.position,
+        ))
+    }
+
+    /// The (chr, pos, ref, alt) of the variant.
+    pub fn vcf_coordinates(&self) -> (String, u32, String, String) {
+        (
+            self.chr.clone(),
+            self.position,
+            self.ref_allele.clone(),
+            self.alt_allele.clone(),
+        )
+    }
+
+    pub fn ensembl_gene_id(&self) -> Option<&str> {
+        self.ensembl_gene_id.as_deref()
+    }
+
+    pub fn entrez_gene_id(&self) -> Option<&str> {
+        self.entrez_gene_id.as_deref()
+    }
+
+    pub fn ccds_id(&self) -> Option<&str> {
+        self.ccds_id.as_deref()
+    }
+
+    pub fn ucsc_id(&self) -> Option<&str> {
+        self.ucsc_id.as_deref()
+    }
+
+    pub fn transcript_url(&self) -> Option<&str> {
+        self.transcript_url.as_deref()
+    }
+
+    pub fn submitted_variant(&self) -> Option<&str> {
+        self.submitted_variant.as_deref()
+    }
+
+    pub fn normalized_hgvs(&self) -> Option<&str> {
+        self.normalized_hgvs.as_deref()
+    }
+
+    /// Whether VariantValidator changed the submitted variant (e.g. its case or whitespace) when normalizing it.
+    ///
+    /// This is false if either the submitted or the normalized variant is unknown.
+    pub fn was_normalized(&self) -> bool {
+        match (self.submitted_variant(), self.normalized_hgvs()) {
+            (Some(submitted_variant), Some(normalized_hgvs)) => {
+                submitted_variant != normalized_hgvs
+            }
+            _ => false,
+        }
+    }
+
+    pub fn validation_warnings(&self) -> Vec<ValidationWarning> {
+        self.validation_warnings
+            .iter()
+            .map(ValidationWarning::new)
+            .collect()
+    }
+
+    /// The Diagnostics of the validated variant, which only has advisory warnings.
+    pub fn diagnostics(&self) -> Diagnostics {
+        Diagnostics::from_validation_warnings(self.validation_warnings())
+    }
+
+    /// The validation warnings as a JSON array, with an object for each warning as given by `ValidationWarning::to_json`.
+    pub fn warnings_as_json(&self) -> serde_json::Value {
+        serde_json::Value::Array(
+            self.validation_warnings()
+                .iter()
+                .map(ValidationWarning::to_json)
+                .collect(),
+        )
+    }
+
+    pub fn transcript_selection(&self) -> TranscriptSelection {
+        self.transcript_selection
+    }
+
+    /// The HGVS on an outdated transcript version, if this variant was validated on the latest version instead.
+    pub fn upgraded_from(&self) -> Option<&str> {
+        self.upgraded_from.as_deref()
+    }
+
+    pub fn selected_assembly(&self) -> Option<&str> {
+        self.selected_assembly.as_deref()
+    }
+
+    /// Whether the variant was validated without any validation warnings, e.g. a TranscriptVersionWarning.
+    pub fn is_clean(&self) -> bool {
+        self.validation_warnings.is_empty()
+    }
+
+    /// Whether both describe the same validated variant, ignoring the provenance and annotation of the validation
+    /// (e.g. the submitted variant or the validation warnings). Unlike `==`, only the following are compared:
+    /// assembly, chromosome, position, alleles, gene, transcript, allele and the genomic and protein HGVS.
+    pub fn core_eq(&self, other: &Self) -> bool {
+        self.assembly == other.assembly
+            && self.chr == other.chr
+            && self.position == other.position
+            && self.ref_allele == other.ref_allele
+            && self.alt_allele == other.alt_allele
+            && self.symbol == other.symbol
+            && self.hgnc_id == other.hgnc_id
+            && self.transcript == other.transcript
+            && self.allele == other.allele
+            && self.g_hgvs == other.g_hgvs
+            && self.p_hgvs == other.p_hgvs
+    }
+
+    /// Whether the variant is on a coding transcript (c.), a non-coding transcript (n.) or the mitochondrial genome (m.).
+    pub fn hgvs_syntax(&self) -> Option<HgvsSyntax> {
+        HgvsSyntax::from_allele(self.allele())
+    }
+
+    /// The class of the variant (e.g. Snv or Deletion), as given by its allele.
+    pub fn variant_class(&self) -> VariantClass {
+        VariantClass::from_allele(self.allele())
+    }
+
+    /// The molecule context of the transcript HGVS: Transcript for c. and n. variants, and Genomic for m. variants.
+    ///
+    /// The VariationDescriptor of a VariantInterpretation always has the Genomic molecule context instead, since it carries the VCF record.
+    pub fn transcript_molecule_context(&self) -> MoleculeContext {
+        match self.hgvs_syntax() {
+            Some(HgvsSyntax::Coding | HgvsSyntax::NonCoding) => MoleculeContext::Transcript,
+            Some(HgvsSyntax::Mitochondrial) => MoleculeContext::Genomic,
+            None => MoleculeContext::UnspecifiedMoleculeContext,
+        }
+    }
+
+    /// A variant is intronic if one of its positions has an offset from an exon, e.g., c.123+4A>G or c.123-2A>G.
+    pub fn is_intronic(&self) -> bool {
+        if !is_c_hgvs(self.allele()) && !is_n_hgvs(self.allele()) {
+            return false;
+        }
+        INTRONIC_OFFSET_REGEX.is_match(&self.allele[2..])
+    }
+
     pub fn is_x_chromosomal(&self) -> bool {
         self.chr.contains("X")
     }
@@ -129,46 +571,99 @@ impl HgvsVariant {
         self.chr.contains("Y")
     }
 
-    /// Create Phenopacket VariantInterpretation from a ValidatedHgvs and an allele count.
-    /// Throws an error if the allele count is not 1 or 2.
-    pub fn create_variant_interpretation(
-        &self,
-        allele_count: AlleleCount,
-        sex: &ChromosomalSex,
-    ) -> Result<VariantInterpretation, HGVSError> {
-        let gene_context = GeneDescriptor {
-            value_id: self.hgnc_id().to_string(),
-            symbol: self.gene_symbol().to_string(),
-            ..Default::default()
+    /// The header of the rows given by to_tsv_row.
+    pub fn tsv_header() -> &'static str {
+        "assembly\tchr\tpos\tref\talt\tgene_symbol\thgnc_id\ttranscript\tc_or_n_hgvs\tg_hgvs\tp_hgvs"
+    }
+
+    /// A tab-separated row with the columns given by tsv_header. The p_hgvs cell is empty if there is no protein HGVS.
+    ///
+    /// Backslashes, tabs and newlines within a cell are escaped as `\\`, `\t` and `\n`.
+    pub fn to_tsv_row(&self) -> String {
+        let position = self.position.to_string();
+        let cells = [
+            self.assembly(),
+            self.chr(),
+            position.as_str(),
+            self.ref_allele(),
+            self.alt_allele(),
+            self.gene_symbol(),
+            self.hgnc_id(),
+            self.transcript(),
+            self.transcript_hgvs(),
+            self.g_hgvs(),
+            self.p_hgvs.as_deref().unwrap_or_default(),
+        ];
+        cells.map(escape_tsv_cell).join("\t")
+    }
+
+    /// Parses a row given by to_tsv_row. The allele is taken from the c_or_n_hgvs column.
+    ///
+    /// Only the columns of the row are kept, so the other fields (e.g. the validation warnings) are empty,
+    /// and the parsed HgvsVariant is only core_eq to the one which gave the row.
+    pub fn from_tsv_row(row: &str) -> Result<Self, HGVSError> {
+        let invalid_row = |problem: String| HGVSError::InvalidTsvRow {
+            row: row.to_string(),
+            problem,
         };
 
-        let mut expressions = vec![];
+        let cells = row
+            .split('\t')
+            .map(unescape_tsv_cell)
+            .collect::<Vec<String>>();
+        let n_columns = HgvsVariant::tsv_header().split('\t').count();
+        let [
+            assembly,
+            chr,
+            position,
+            ref_allele,
+            alt_allele,
+            symbol,
+            hgnc_id,
+            transcript,
+            transcript_hgvs,
+            g_hgvs,
+            p_hgvs,
+        ]: [String; 11] = cells.try_into().map_err(|cells: Vec<String>| {
+            invalid_row(format!(
+                "expected {n_columns} columns but found {}",
+                cells.len()
+            ))
+        })?;
 
-        if is_c_hgvs(self.allele()) {
-            let hgvs_c = Expression {
-                syntax: "hgvs.c".to_string(),
-                value: self.transcript_hgvs().to_string(),
-                version: String::default(),
-            };
-            expressions.push(hgvs_c);
-        }
+        let position = position
+            .parse::<u32>()
+            .map_err(|_| invalid_row(format!("position {position} is not parseable to u32")))?;
+        let (_, allele) = get_transcript_and_allele(&transcript_hgvs)?;
+        let allele = allele.to_string();
 
-        if is_n_hgvs(self.allele()) {
-            let hgvs_n = Expression {
-                syntax: "hgvs.n".to_string(),
-                value: self.transcript_hgvs().to_string(),
-                version: String::default(),
-            };
-            expressions.push(hgvs_n);
-        }
+        Ok(HgvsVariant::new(
+            assembly,
+            chr,
+            position,
+            ref_allele,
+            alt_allele,
+            symbol,
+            hgnc_id,
+            transcript,
+            allele,
+            transcript_hgvs,
+            g_hgvs,
+            non_empty(p_hgvs),
+        ))
+    }
+
+    /// The HGVS expressions of the variant, which are always in the order:
+    /// transcript (hgvs.c, hgvs.n or hgvs.m), then genomic (hgvs.g), then protein (hgvs.p) if available.
+    fn expressions(&self) -> Vec<Expression> {
+        let mut expressions = vec![];
 
-        if is_m_hgvs(self.allele()) {
-            let hgvs_m = Expression {
-                syntax: "hgvs.m".to_string(),
+        if let Some(hgvs_syntax) = self.hgvs_syntax() {
+            expressions.push(Expression {
+                syntax: hgvs_syntax.as_str().to_string(),
                 value: self.transcript_hgvs().to_string(),
                 version: String::default(),
-            };
-            expressions.push(hgvs_m);
+            });
         }
 
         expressions.push(Expression {
@@ -177,15 +672,42 @@ impl HgvsVariant {
             version: String::default(),
         });
 
-        if let Some(hgvs_p) = &self.p_hgvs() {
-            let hgvs_p = Expression {
+        if let Some(hgvs_p) = self.p_hgvs() {
+            expressions.push(Expression {
                 syntax: "hgvs.p".to_string(),
-                value: hgvs_p.clone(),
+                value: hgvs_p,
                 version: String::default(),
-            };
-            expressions.push(hgvs_p);
+            });
         }
 
+        expressions
+    }
+
+    /// Create Phenopacket VariantInterpretation from a ValidatedHgvs and an allele count.
+    /// Throws an error if the allele count is not 1 or 2.
+    pub fn create_variant_interpretation(
+        &self,
+        allele_count: AlleleCount,
+        sex: &ChromosomalSex,
+    ) -> Result<VariantInterpretation, HGVSError> {
+        let allelic_state = self.get_allelic_state(allele_count, sex)?;
+        Ok(self.create_variant_interpretation_with_state(allelic_state))
+    }
+
+    /// As create_variant_interpretation, but with the given allelic state (e.g. a GENO term for a de novo variant)
+    /// instead of one derived from the allele count and chromosomal sex.
+    pub fn create_variant_interpretation_with_state(
+        &self,
+        allelic_state: OntologyClass,
+    ) -> VariantInterpretation {
+        let gene_context = GeneDescriptor {
+            value_id: self.hgnc_id().to_string(),
+            symbol: self.gene_symbol().to_string(),
+            ..Default::default()
+        };
+
+        let expressions = self.expressions();
+
         let vcf_record = VcfRecord {
             genome_assembly: self.assembly().to_string(),
             chrom: self.chr().to_string(),
@@ -195,13 +717,6 @@ impl HgvsVariant {
             ..Default::default()
         };
 
-        let allelic_state = Self::get_allele_term(
-            sex,
-            allele_count,
-            self.is_x_chromosomal(),
-            self.is_y_chromosomal(),
-        )?;
-
         let variation_descriptor = VariationDescriptor {
             id: Uuid::new_v4().to_string(),
             gene_context: Some(gene_context),
@@ -211,11 +726,36 @@ impl HgvsVariant {
             allelic_state: Some(allelic_state),
             ..Default::default()
         };
-        Ok(VariantInterpretation {
+        VariantInterpretation {
             acmg_pathogenicity_classification: AcmgPathogenicityClassification::Pathogenic.into(),
             therapeutic_actionability: TherapeuticActionability::UnknownActionability.into(),
             variation_descriptor: Some(variation_descriptor),
-        })
+        }
+    }
+
+    /// Checks that the allele count is possible for a variant on this chromosome in an individual of the given chromosomal sex,
+    /// e.g. a male (XY) individual can't have two alleles of a variant on the X chromosome.
+    ///
+    /// `create_variant_interpretation` returns the same error for these combinations.
+    pub fn check_allelic_consistency(
+        &self,
+        allele_count: AlleleCount,
+        sex: &ChromosomalSex,
+    ) -> Result<(), HGVSError> {
+        self.get_allelic_state(allele_count, sex).map(|_| ())
+    }
+
+    fn get_allelic_state(
+        &self,
+        allele_count: AlleleCount,
+        sex: &ChromosomalSex,
+    ) -> Result<OntologyClass, HGVSError> {
+        Self::get_allele_term(
+            sex,
+            allele_count,
+            self.is_x_chromosomal(),
+            self.is_y_chromosomal(),
+        )
     }
 
     fn get_allele_term(
@@ -320,13 +860,45 @@ impl HgvsVariant {
     }
 }
 
+fn escape_tsv_cell(cell: &str) -> String {
+    cell.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape_tsv_cell(cell: &str) -> String {
+    let mut unescaped = String::with_capacity(cell.len());
+    let mut chars = cell.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::hgvs::enums::{AlleleCount, ChromosomalSex};
+    use crate::hgvs::enums::{
+        AlleleCount, ChromosomalSex, GenomeAssembly, HgvsSyntax, VariantClass,
+    };
+    use crate::hgvs::error::HGVSError;
+    use crate::hgvs::genomic_locus::GenomicLocus;
     use crate::hgvs::hgvs_client::HGVSClient;
     use crate::hgvs::hgvs_variant::HgvsVariant;
+    use crate::hgvs::json_schema::{SingleVariantInfo, SingleVariantResponse};
+    use crate::hgvs::test_utils::{SingleVariantResponseBuilder, load_response};
     use crate::hgvs::traits::HGVSData;
-    use phenopackets::ga4gh::vrsatile::v1::Expression;
+    use crate::hgvs::transcript_selection::TranscriptSelection;
+    use phenopackets::ga4gh::vrsatile::v1::{Expression, MoleculeContext};
+    use phenopackets::schema::v2::core::OntologyClass;
     use rstest::{fixture, rstest};
 
     #[fixture]
@@ -477,6 +1049,47 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[rstest]
+    fn test_check_allelic_consistency() {
+        let x_chromosomal_variant = HgvsVariant::new(
+            "hg38",
+            "chrX",
+            31819974,
+            "G",
+            "A",
+            "DMD",
+            "HGNC:2928",
+            "NM_004006.3",
+            "c.10108C>T",
+            "NM_004006.3:c.10108C>T",
+            "NC_000023.11:g.31819974G>A",
+            None::<&str>,
+        );
+
+        x_chromosomal_variant
+            .check_allelic_consistency(AlleleCount::Single, &ChromosomalSex::XY)
+            .unwrap();
+        assert!(matches!(
+            x_chromosomal_variant
+                .check_allelic_consistency(AlleleCount::Double, &ChromosomalSex::XY),
+            Err(HGVSError::ContradictoryAllelicData { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_create_variant_interpretation_with_state() {
+        let allelic_state = OntologyClass {
+            id: "GENO:0000402".to_string(),
+            label: "compound heterozygous".to_string(),
+        };
+
+        let vi = validated_c_hgvs().create_variant_interpretation_with_state(allelic_state.clone());
+
+        let variation_descriptor = vi.variation_descriptor.unwrap();
+        assert_eq!(variation_descriptor.allelic_state, Some(allelic_state));
+        assert_eq!(variation_descriptor.vcf_record.unwrap().chrom, "chr12");
+    }
+
     #[rstest]
     fn test_create_variant_interpretation_c_hgvs() {
         let vi = validated_c_hgvs()
@@ -526,4 +1139,579 @@ mod tests {
         let n_hgvs_expression = n_hgvs_expressions.first().unwrap();
         assert_eq!(n_hgvs_expression.value, validated_n_hgvs().transcript_hgvs);
     }
+
+    #[rstest]
+    #[case(GenomeAssembly::Hg38, 39332405)]
+    #[case(GenomeAssembly::Hg19, 39726207)]
+    fn test_from_single_variant_info(#[case] assembly: GenomeAssembly, #[case] position: u32) {
+        let response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+
+        let hgvs_variant = HgvsVariant::from_single_variant_info(
+            &response.variant_info,
+            "NM_001173464.1:c.2860C>T",
+            assembly,
+        )
+        .unwrap();
+
+        assert_eq!(hgvs_variant.assembly(), assembly.to_string());
+        assert_eq!(hgvs_variant.position(), position);
+        assert_eq!(hgvs_variant.transcript(), "NM_001173464.1");
+        assert_eq!(hgvs_variant.allele(), "c.2860C>T");
+        assert_eq!(
+            hgvs_variant.p_hgvs(),
+            Some("NP_001166935.1:p.(Arg954Trp)".to_string())
+        );
+        assert_eq!(hgvs_variant.ensembl_gene_id(), Some("ENSG00000139116"));
+        assert_eq!(hgvs_variant.entrez_gene_id(), Some("55605"));
+        assert_eq!(hgvs_variant.ccds_id(), Some("CCDS53776.1"));
+        assert_eq!(hgvs_variant.ucsc_id(), Some("uc001rly.4"));
+        assert_eq!(
+            hgvs_variant.transcript_url(),
+            Some("https://www.ncbi.nlm.nih.gov/nuccore/NM_001173464.1")
+        );
+    }
+
+    #[rstest]
+    fn test_from_single_variant_info_built_response() {
+        let response = SingleVariantResponseBuilder::default()
+            .transcript_hgvs("NR_002196.1:n.601G>T")
+            .locus(
+                "hg19",
+                "chr11",
+                2016406,
+                "C",
+                "A",
+                "NC_000011.9:g.2016406C>A",
+            )
+            .gene_symbol("H19")
+            .hgnc_id("HGNC:4713")
+            .p_hgvs("")
+            .build();
+
+        let hgvs_variant = HgvsVariant::from_single_variant_info(
+            &response.variant_info,
+            "NR_002196.1:n.601G>T",
+            GenomeAssembly::Hg19,
+        )
+        .unwrap();
+
+        assert_eq!(
+            hgvs_variant.vcf_coordinates(),
+            (
+                "chr11".to_string(),
+                2016406,
+                "C".to_string(),
+                "A".to_string()
+            )
+        );
+        assert_eq!(hgvs_variant.gene_symbol(), "H19");
+        assert_eq!(hgvs_variant.hgnc_id(), "HGNC:4713");
+        assert_eq!(hgvs_variant.p_hgvs(), None);
+        assert!(hgvs_variant.is_clean());
+    }
+
+    #[rstest]
+    fn test_from_single_variant_info_provenance() {
+        let mut response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+        response.variant_info.submitted_variant = "NM_001173464:c.2860C>T".to_string();
+
+        let hgvs_variant = HgvsVariant::from_single_variant_info(
+            &response.variant_info,
+            "NM_001173464.1:c.2860C>T",
+            GenomeAssembly::Hg38,
+        )
+        .unwrap();
+
+        assert_eq!(
+            hgvs_variant.submitted_variant(),
+            Some("NM_001173464:c.2860C>T")
+        );
+        assert_ne!(
+            hgvs_variant.submitted_variant(),
+            Some(response.variant_info.hgvs_transcript_variant.as_str())
+        );
+        assert_eq!(hgvs_variant.selected_assembly(), Some("GRCh38"));
+    }
+
+    #[rstest]
+    fn test_was_normalized() {
+        let mut response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+        response.variant_info.submitted_variant = "nm_001173464.1: c.2860C>T".to_string();
+
+        let hgvs_variant = HgvsVariant::from_single_variant_info(
+            &response.variant_info,
+            "NM_001173464.1:c.2860C>T",
+            GenomeAssembly::Hg38,
+        )
+        .unwrap();
+
+        assert_eq!(
+            hgvs_variant.submitted_variant(),
+            Some("nm_001173464.1: c.2860C>T")
+        );
+        assert_eq!(
+            hgvs_variant.normalized_hgvs(),
+            Some("NM_001173464.1:c.2860C>T")
+        );
+        assert!(hgvs_variant.was_normalized());
+    }
+
+    #[rstest]
+    fn test_was_not_normalized() {
+        let response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+
+        let hgvs_variant = HgvsVariant::from_single_variant_info(
+            &response.variant_info,
+            "NM_001173464.1:c.2860C>T",
+            GenomeAssembly::Hg38,
+        )
+        .unwrap();
+
+        assert!(!hgvs_variant.was_normalized());
+        assert!(!validated_c_hgvs().was_normalized());
+    }
+
+    #[rstest]
+    fn test_core_eq_ignores_provenance() {
+        let hgvs_variant = validated_c_hgvs().with_submitted_variant("NM_001173464.1:c.2860C>T");
+        let other_hgvs_variant = validated_c_hgvs()
+            .with_submitted_variant("NM_001173464:c.2860C>T")
+            .with_selected_assembly("GRCh38");
+
+        assert!(hgvs_variant.core_eq(&other_hgvs_variant));
+        assert_ne!(hgvs_variant, other_hgvs_variant);
+    }
+
+    #[rstest]
+    fn test_core_eq_different_variants() {
+        assert!(!validated_c_hgvs().core_eq(&validated_n_hgvs()));
+    }
+
+    #[rstest]
+    fn test_warnings_as_json() {
+        let response =
+            SingleVariantResponse::try_from(load_response("kif21a_transcript_version_warning"))
+                .unwrap();
+        let hgvs_variant = HgvsVariant::from_single_variant_info(
+            &response.variant_info,
+            "NM_001173464.1:c.2860C>T",
+            GenomeAssembly::Hg38,
+        )
+        .unwrap();
+
+        let warnings_json = hgvs_variant.warnings_as_json();
+
+        let transcript_version_warnings = warnings_json
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|warning| warning["kind"] == "TranscriptVersionWarning")
+            .collect::<Vec<_>>();
+        assert_eq!(transcript_version_warnings.len(), 1);
+        let warning = transcript_version_warnings[0];
+        assert_eq!(warning["latest_transcript"], "NM_001173464.2");
+        assert!(
+            warning["message"]
+                .as_str()
+                .unwrap()
+                .contains("NM_001173464.1")
+        );
+    }
+
+    #[rstest]
+    fn test_transcript_selection() {
+        let response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+        let hgvs_variant = HgvsVariant::from_single_variant_info(
+            &response.variant_info,
+            "NM_001173464.1:c.2860C>T",
+            GenomeAssembly::Hg38,
+        )
+        .unwrap();
+
+        assert_eq!(
+            hgvs_variant.transcript_selection(),
+            TranscriptSelection::default()
+        );
+        assert!(!hgvs_variant.transcript_selection().is_selected());
+    }
+
+    #[rstest]
+    fn test_transcript_selection_mane_select() {
+        let mut response = SingleVariantResponseBuilder::default().build();
+        response.variant_info.annotations.mane_select = true;
+
+        let hgvs_variant = HgvsVariant::from_single_variant_info(
+            &response.variant_info,
+            "NM_001173464.1:c.2860C>T",
+            GenomeAssembly::Hg38,
+        )
+        .unwrap();
+
+        let transcript_selection = hgvs_variant.transcript_selection();
+        assert!(transcript_selection.mane_select());
+        assert!(!transcript_selection.refseq_select());
+        assert!(!transcript_selection.ensembl_select());
+        assert!(transcript_selection.is_selected());
+    }
+
+    #[rstest]
+    fn test_from_single_variant_info_empty_ids() {
+        let mut response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+        response.variant_info.gene_ids.ensembl_gene_id = String::new();
+        response.variant_info.gene_ids.entrez_gene_id = String::new();
+        response.variant_info.gene_ids.ucsc_id = String::new();
+        response.variant_info.annotations.db_xref.ccds = None;
+        response.variant_info.reference_sequence_records = Some(serde_json::json!([]));
+
+        let hgvs_variant = HgvsVariant::from_single_variant_info(
+            &response.variant_info,
+            "NM_001173464.1:c.2860C>T",
+            GenomeAssembly::Hg38,
+        )
+        .unwrap();
+
+        assert_eq!(hgvs_variant.ensembl_gene_id(), None);
+        assert_eq!(hgvs_variant.entrez_gene_id(), None);
+        assert_eq!(hgvs_variant.ccds_id(), None);
+        assert_eq!(hgvs_variant.ucsc_id(), None);
+        assert_eq!(hgvs_variant.transcript_url(), None);
+    }
+
+    #[rstest]
+    fn test_from_single_variant_info_missing_assembly() {
+        let info = SingleVariantInfo::default();
+
+        let result = HgvsVariant::from_single_variant_info(
+            &info,
+            "NM_001173464.1:c.2860C>T",
+            GenomeAssembly::Hg38,
+        );
+
+        assert!(matches!(
+            result,
+            Err(HGVSError::GenomeAssemblyNotFound { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case(GenomeAssembly::Hg38)]
+    #[case(GenomeAssembly::Hg19)]
+    fn test_new_typed_round_trip(#[case] assembly: GenomeAssembly) {
+        let hgvs_variant = HgvsVariant::new_typed(
+            assembly,
+            "chr11",
+            1997235,
+            "C",
+            "A",
+            "H19",
+            "HGNC:4713",
+            "NR_002196.1",
+            "n.601G>T",
+            "NR_002196.1:n.601G>T",
+            "NC_000011.10:g.1997235C>A",
+            None::<&str>,
+        );
+
+        assert_eq!(hgvs_variant.assembly(), assembly.to_string());
+        assert_eq!(hgvs_variant.assembly_enum(), Some(assembly));
+    }
+
+    #[rstest]
+    fn test_assembly_enum_unsupported() {
+        let hgvs_variant = HgvsVariant::new(
+            "grch36",
+            "chr11",
+            1997235,
+            "C",
+            "A",
+            "H19",
+            "HGNC:4713",
+            "NR_002196.1",
+            "n.601G>T",
+            "NR_002196.1:n.601G>T",
+            "NC_000011.10:g.1997235C>A",
+            None::<&str>,
+        );
+
+        assert_eq!(hgvs_variant.assembly_enum(), None);
+    }
+
+    fn brca2_indel(ref_allele: &str, alt_allele: &str, allele: &str, g_hgvs: &str) -> HgvsVariant {
+        HgvsVariant::new(
+            "hg38",
+            "chr13",
+            32398521,
+            ref_allele,
+            alt_allele,
+            "BRCA2",
+            "HGNC:1101",
+            "NM_000059.4",
+            allele,
+            format!("NM_000059.4:{allele}"),
+            g_hgvs,
+            None::<&str>,
+        )
+    }
+
+    #[rstest]
+    fn test_genomic_span_snv() {
+        let hgvs_variant = validated_c_hgvs();
+
+        let (start, end) = hgvs_variant.genomic_span();
+
+        assert_eq!(start, hgvs_variant.position());
+        assert_eq!(end - start + 1, 1);
+    }
+
+    #[rstest]
+    fn test_genomic_span_deletion() {
+        let hgvs_variant = brca2_indel(
+            "TGA",
+            "T",
+            "c.11031_11032delGA",
+            "NC_000013.11:g.32398522_32398523del",
+        );
+
+        assert_eq!(hgvs_variant.genomic_span(), (32398522, 32398523));
+    }
+
+    #[rstest]
+    fn test_genomic_span_insertion() {
+        let hgvs_variant = brca2_indel(
+            "T",
+            "TGA",
+            "c.11030_11031insGA",
+            "NC_000013.11:g.32398521_32398522insGA",
+        );
+
+        assert_eq!(hgvs_variant.genomic_span(), (32398521, 32398522));
+    }
+
+    #[rstest]
+    fn test_variant_class() {
+        assert_eq!(validated_c_hgvs().variant_class(), VariantClass::Snv);
+        assert_eq!(validated_n_hgvs().variant_class(), VariantClass::Snv);
+    }
+
+    #[rstest]
+    fn test_genomic_locus() {
+        let mut variants = [validated_c_hgvs(), validated_n_hgvs()];
+
+        variants.sort_by_key(|variant| variant.genomic_locus().unwrap());
+
+        assert_eq!(variants[0].gene_symbol(), "H19");
+        assert_eq!(
+            variants[1].genomic_locus().unwrap(),
+            GenomicLocus::new(GenomeAssembly::Hg38, "chr12", 38332495)
+        );
+    }
+
+    #[fixture]
+    fn validated_m_hgvs() -> HgvsVariant {
+        HgvsVariant::new(
+            "hg38",
+            "chrM",
+            3243,
+            "A",
+            "G",
+            "MT-TL1",
+            "HGNC:7490",
+            "NC_012920.1",
+            "m.3243A>G",
+            "NC_012920.1:m.3243A>G",
+            "NC_012920.1:g.3243A>G",
+            None::<&str>,
+        )
+    }
+
+    #[rstest]
+    #[case(validated_c_hgvs())]
+    #[case(validated_n_hgvs())]
+    #[case(validated_m_hgvs())]
+    fn test_variant_interpretation_molecule_context(#[case] hgvs_variant: HgvsVariant) {
+        let vi = hgvs_variant
+            .create_variant_interpretation(AlleleCount::Single, &ChromosomalSex::XX)
+            .unwrap();
+
+        assert_eq!(
+            vi.variation_descriptor.unwrap().molecule_context,
+            i32::from(MoleculeContext::Genomic)
+        );
+    }
+
+    #[rstest]
+    #[case(validated_c_hgvs(), vec!["hgvs.c", "hgvs.g", "hgvs.p"])]
+    #[case(validated_n_hgvs(), vec!["hgvs.n", "hgvs.g"])]
+    #[case(validated_m_hgvs(), vec!["hgvs.m", "hgvs.g"])]
+    fn test_expression_order(#[case] hgvs_variant: HgvsVariant, #[case] expected: Vec<&str>) {
+        let vi = hgvs_variant
+            .create_variant_interpretation(AlleleCount::Single, &ChromosomalSex::XX)
+            .unwrap();
+
+        let syntaxes = vi
+            .variation_descriptor
+            .unwrap()
+            .expressions
+            .into_iter()
+            .map(|expression| expression.syntax)
+            .collect::<Vec<String>>();
+        assert_eq!(syntaxes, expected);
+    }
+
+    #[rstest]
+    fn test_to_tsv_row() {
+        let response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+        let hgvs_variant = HgvsVariant::from_single_variant_info(
+            &response.variant_info,
+            "NM_001173464.1:c.2860C>T",
+            GenomeAssembly::Hg38,
+        )
+        .unwrap();
+
+        assert_eq!(
+            HgvsVariant::tsv_header(),
+            "assembly\tchr\tpos\tref\talt\tgene_symbol\thgnc_id\ttranscript\tc_or_n_hgvs\tg_hgvs\tp_hgvs"
+        );
+        assert_eq!(
+            hgvs_variant.to_tsv_row(),
+            "hg38\tchr12\t39332405\tG\tA\tKIF21A\tHGNC:19349\tNM_001173464.1\tNM_001173464.1:c.2860C>T\tNC_000012.12:g.39332405G>A\tNP_001166935.1:p.(Arg954Trp)"
+        );
+    }
+
+    #[rstest]
+    fn test_to_tsv_row_escapes_and_empty_p_hgvs() {
+        let mut hgvs_variant = validated_n_hgvs();
+        hgvs_variant.symbol = "H19\tH19".to_string();
+
+        let row = hgvs_variant.to_tsv_row();
+
+        assert_eq!(row.split('\t').count(), 11);
+        assert!(row.contains("H19\\tH19"));
+        assert!(row.ends_with("NC_000011.10:g.1997235C>A\t"));
+    }
+
+    #[rstest]
+    #[case(validated_c_hgvs())]
+    #[case(validated_n_hgvs())]
+    fn test_tsv_row_round_trip(#[case] hgvs_variant: HgvsVariant) {
+        let row = hgvs_variant.to_tsv_row();
+
+        assert_eq!(HgvsVariant::from_tsv_row(&row).unwrap(), hgvs_variant);
+    }
+
+    #[fixture]
+    fn annotated_c_hgvs() -> HgvsVariant {
+        validated_c_hgvs()
+            .with_transcript_description(
+                "Homo sapiens kinesin family member 21A (KIF21A), transcript variant 1, mRNA",
+            )
+            .with_ensembl_gene_id("ENSG00000139116")
+            .with_entrez_gene_id("55605")
+            .with_ccds_id("CCDS53776.1")
+            .with_ucsc_id("uc001rly.4")
+            .with_transcript_url("https://www.ncbi.nlm.nih.gov/nuccore/NM_001173464.1")
+            .with_submitted_variant("NM_001173464:c.2860C>T")
+            .with_normalized_hgvs("NM_001173464.1:c.2860C>T")
+            .with_selected_assembly("GRCh38")
+            .with_validation_warnings(vec![
+                "TranscriptVersionWarning: A more recent version of the selected reference sequence NM_001173464.1 is available (NM_001173464.2)".to_string(),
+            ])
+            .with_upgraded_from("NM_001173464.1:c.2860C>T")
+            .with_transcript_selection(TranscriptSelection::new(true, false, true))
+    }
+
+    #[rstest]
+    fn test_tsv_row_round_trip_annotated(annotated_c_hgvs: HgvsVariant) {
+        let row = annotated_c_hgvs.to_tsv_row();
+
+        let parsed_hgvs_variant = HgvsVariant::from_tsv_row(&row).unwrap();
+
+        assert!(parsed_hgvs_variant.core_eq(&annotated_c_hgvs));
+        assert_eq!(parsed_hgvs_variant.submitted_variant(), None);
+        assert_eq!(parsed_hgvs_variant.selected_assembly(), None);
+        assert!(parsed_hgvs_variant.validation_warnings().is_empty());
+    }
+
+    #[rstest]
+    #[case(validated_n_hgvs())]
+    #[case(annotated_c_hgvs())]
+    fn test_serde_round_trip(#[case] hgvs_variant: HgvsVariant) {
+        let serialized = serde_json::to_string(&hgvs_variant).unwrap();
+
+        let deserialized_hgvs_variant = serde_json::from_str::<HgvsVariant>(&serialized).unwrap();
+
+        assert_eq!(deserialized_hgvs_variant, hgvs_variant);
+    }
+
+    #[rstest]
+    fn test_tsv_row_round_trip_escaped() {
+        let mut hgvs_variant = validated_n_hgvs();
+        hgvs_variant.symbol = "H19\\\tH19".to_string();
+
+        let row = hgvs_variant.to_tsv_row();
+
+        assert_eq!(HgvsVariant::from_tsv_row(&row).unwrap(), hgvs_variant);
+    }
+
+    #[rstest]
+    #[case("hg38\tchr12\t39332405")]
+    #[case(
+        "hg38\tchr12\tposition\tG\tA\tKIF21A\tHGNC:19349\tNM_001173464.1\tNM_001173464.1:c.2860C>T\tNC_000012.12:g.39332405G>A\t"
+    )]
+    fn test_from_tsv_row_err(#[case] row: &str) {
+        assert!(matches!(
+            HgvsVariant::from_tsv_row(row),
+            Err(HGVSError::InvalidTsvRow { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_hgvs_syntax() {
+        assert_eq!(validated_c_hgvs().hgvs_syntax(), Some(HgvsSyntax::Coding));
+        assert_eq!(
+            validated_n_hgvs().hgvs_syntax(),
+            Some(HgvsSyntax::NonCoding)
+        );
+        assert_eq!(validated_n_hgvs().hgvs_syntax().unwrap().as_str(), "hgvs.n");
+    }
+
+    #[rstest]
+    fn test_transcript_molecule_context() {
+        assert_eq!(
+            validated_c_hgvs().transcript_molecule_context(),
+            MoleculeContext::Transcript
+        );
+        assert_eq!(
+            validated_n_hgvs().transcript_molecule_context(),
+            MoleculeContext::Transcript
+        );
+        assert_eq!(
+            validated_m_hgvs().transcript_molecule_context(),
+            MoleculeContext::Genomic
+        );
+    }
+
+    #[rstest]
+    #[case("c.123+4A>G", true)]
+    #[case("c.123-2A>G", true)]
+    #[case("c.2860C>T", false)]
+    #[case("c.-14C>T", false)]
+    #[case("c.100_123+2del", true)]
+    fn test_is_intronic(#[case] allele: &str, #[case] expected: bool) {
+        let hgvs_variant = HgvsVariant::new(
+            "hg38",
+            "chr12",
+            39332405,
+            "G",
+            "A",
+            "KIF21A",
+            "HGNC:19349",
+            "NM_001173464.1",
+            allele,
+            format!("NM_001173464.1:{allele}"),
+            "NC_000012.12:g.39332405G>A",
+            None::<&str>,
+        );
+        assert_eq!(hgvs_variant.is_intronic(), expected);
+    }
 }
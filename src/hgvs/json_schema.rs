@@ -1,5 +1,11 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::hgvs::dual_assembly_variant::DualAssemblyVariant;
+use crate::hgvs::enums::{GenomeAssembly, ResponseFlag};
+use crate::hgvs::error::HGVSError;
+use crate::hgvs::hgvs_variant::HgvsVariant;
+use crate::hgvs::utils::non_empty;
+use log::warn;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeSet, HashMap};
 
 /// If the request is a success, a response with the following structure will be returned
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -10,6 +16,179 @@ pub struct VariantValidatorResponse {
     pub metadata: Metadata,
 }
 
+impl VariantValidatorResponse {
+    pub fn response_flag(&self) -> ResponseFlag {
+        ResponseFlag::from(self.flag.as_str())
+    }
+
+    /// Splits the response into a SingleVariantResponse for every variant it has information on, ordered by their keys.
+    pub fn into_all_responses(self) -> Vec<SingleVariantResponse> {
+        let mut variant_infos = self.variant_info.into_iter().collect::<Vec<_>>();
+        variant_infos.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
+        variant_infos
+            .into_iter()
+            .map(|(_, variant_info)| SingleVariantResponse {
+                variant_info,
+                flag: self.flag.clone(),
+                metadata: self.metadata.clone(),
+            })
+            .collect()
+    }
+}
+
+/// A VariantValidatorResponse which contains information on exactly one variant
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SingleVariantResponse {
+    pub variant_info: SingleVariantInfo,
+    pub flag: String,
+    pub metadata: Metadata,
+}
+
+impl SingleVariantResponse {
+    /// The genome assemblies (e.g. hg38, grch38) for which the response has a primary assembly locus, in alphabetical order.
+    pub fn available_assemblies(&self) -> Vec<String> {
+        let mut assemblies = self
+            .variant_info
+            .primary_assembly_loci
+            .keys()
+            .cloned()
+            .collect::<Vec<String>>();
+        assemblies.sort();
+        assemblies
+    }
+
+    /// Creates a HgvsVariant for the submitted variant on the given assembly, whose locus may be under either of its names.
+    ///
+    /// If the response has no locus on the assembly, the assembly which the response was selected under is logged.
+    /// If the response only has a locus on one other assembly (e.g. because the variant was requested on that assembly),
+    /// a GenomeAssemblyMismatch error is returned.
+    pub fn abbreviate(&self, assembly: GenomeAssembly) -> Result<HgvsVariant, HGVSError> {
+        let transcript_hgvs = &self.variant_info.submitted_variant;
+
+        let Some(assembly_name) = self.locus_assembly_name(assembly) else {
+            let available_assemblies = self
+                .available_assemblies()
+                .iter()
+                .filter_map(|assembly_name| assembly_name.parse::<GenomeAssembly>().ok())
+                .map(|available_assembly| available_assembly.to_string())
+                .collect::<BTreeSet<String>>();
+            warn!(
+                "Abbreviating VariantValidator response for {} to {}, but it was selected under {} and has loci on {:?}.",
+                transcript_hgvs,
+                assembly,
+                self.variant_info.selected_assembly,
+                available_assemblies
+            );
+            if available_assemblies.len() == 1
+                && let Some(available_assembly) = available_assemblies.first()
+            {
+                return Err(HGVSError::GenomeAssemblyMismatch {
+                    hgvs: transcript_hgvs.clone(),
+                    desired_assembly: assembly.to_string(),
+                    available_assembly: available_assembly.clone(),
+                    selected_assembly: self.variant_info.selected_assembly.clone(),
+                });
+            }
+            return Err(HGVSError::GenomeAssemblyNotFound {
+                hgvs: transcript_hgvs.clone(),
+                desired_assembly: assembly.to_string(),
+                found_assemblies: self.available_assemblies(),
+            });
+        };
+
+        HgvsVariant::from_single_variant_info_with_assembly_name(
+            &self.variant_info,
+            transcript_hgvs,
+            &assembly_name,
+        )
+    }
+
+    /// Creates a HgvsVariant for the submitted variant on the first of the preferred assemblies for which the response has a locus.
+    ///
+    /// An assembly matches a locus under either of its names, as in locus_assembly_name.
+    pub fn abbreviate_with_fallback(
+        self,
+        preferences: &[GenomeAssembly],
+    ) -> Result<HgvsVariant, HGVSError> {
+        let transcript_hgvs = &self.variant_info.submitted_variant;
+
+        let assembly_name = preferences
+            .iter()
+            .find_map(|preference| self.locus_assembly_name(*preference))
+            .ok_or_else(|| HGVSError::GenomeAssemblyNotFound {
+                hgvs: transcript_hgvs.clone(),
+                desired_assembly: preferences
+                    .iter()
+                    .map(GenomeAssembly::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                found_assemblies: self.available_assemblies(),
+            })?;
+
+        HgvsVariant::from_single_variant_info_with_assembly_name(
+            &self.variant_info,
+            transcript_hgvs,
+            &assembly_name,
+        )
+    }
+
+    /// The name under which the response has a locus on the assembly, if it has one.
+    ///
+    /// An assembly matches a locus under either of its names, e.g. GenomeAssembly::Hg38 matches a locus on hg38 or on grch38,
+    /// with the hg38 locus preferred if both are present.
+    fn locus_assembly_name(&self, assembly: GenomeAssembly) -> Option<String> {
+        let available_assemblies = self.available_assemblies();
+        let preferred_name = assembly.to_string();
+        if available_assemblies.contains(&preferred_name) {
+            Some(preferred_name)
+        } else {
+            available_assemblies.into_iter().find(|assembly_name| {
+                assembly_name.parse::<GenomeAssembly>().ok() == Some(assembly)
+            })
+        }
+    }
+
+    /// Creates a DualAssemblyVariant, i.e. the submitted variant on the primary assembly together with its locus on the secondary assembly.
+    pub fn abbreviate_dual(
+        self,
+        primary: GenomeAssembly,
+        secondary: GenomeAssembly,
+    ) -> Result<DualAssemblyVariant, HGVSError> {
+        Ok(DualAssemblyVariant::from_variants(
+            self.abbreviate(primary)?,
+            self.abbreviate(secondary)?,
+        ))
+    }
+
+    /// The corrections which LOVD made to the submitted variant, if any.
+    pub fn lovd_corrections(&self) -> Option<&HashMap<String, u32>> {
+        self.variant_info.lovd_corrections.as_ref()
+    }
+
+    pub fn lovd_messages(&self) -> Option<&LovdMessages> {
+        self.variant_info.lovd_messages.as_ref()
+    }
+
+    /// Whether the variant validated without any validation warnings.
+    ///
+    /// This is the same as HgvsVariant::is_clean for any HgvsVariant abbreviated from the response.
+    pub fn is_clean(&self) -> bool {
+        self.variant_info.validation_warnings.is_empty()
+    }
+}
+
+impl TryFrom<VariantValidatorResponse> for SingleVariantResponse {
+    type Error = HGVSError;
+
+    fn try_from(response: VariantValidatorResponse) -> Result<Self, Self::Error> {
+        let n_found = response.variant_info.len();
+        if n_found != 1 {
+            return Err(HGVSError::WrongNumberOfVariantInfos { n_found });
+        }
+        Ok(response.into_all_responses().remove(0))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(default)]
 pub struct SingleVariantInfo {
@@ -36,6 +215,20 @@ pub struct SingleVariantInfo {
     pub variant_exonic_positions: Option<VariantExonicPositions>,
 }
 
+impl SingleVariantInfo {
+    /// The NCBI URL of the transcript in reference_sequence_records, if there is one.
+    ///
+    /// VariantValidator usually sends the records as an object, but other shapes (e.g. an empty list) are treated as missing.
+    pub fn transcript_url(&self) -> Option<String> {
+        self.reference_sequence_records
+            .as_ref()?
+            .get("transcript")?
+            .as_str()
+            .map(str::to_string)
+            .and_then(non_empty)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(default)]
 pub struct Annotations {
@@ -102,11 +295,27 @@ pub struct PrimaryAssemblyLoci {
 pub struct VcfCoordinates {
     pub alt: String,
     pub chr: String,
+    #[serde(deserialize_with = "deserialize_pos")]
     pub pos: String,
     #[serde(rename = "ref")]
     pub reference: String,
 }
 
+/// VariantValidator usually gives pos as a string, but occasionally as a number.
+fn deserialize_pos<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Pos {
+        String(String),
+        Number(u64),
+    }
+
+    Ok(match Pos::deserialize(deserializer)? {
+        Pos::String(pos) => pos,
+        Pos::Number(pos) => pos.to_string(),
+    })
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(default)]
 pub struct VariantExonicPositions {
@@ -121,6 +330,32 @@ pub struct ExonicPosition {
     pub end_exon: String,
 }
 
+/// The response of the VariantValidator gene2transcripts endpoint for a single gene
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Gene2TranscriptsResponse {
+    pub current_name: String,
+    pub current_symbol: String,
+    pub error: String,
+    pub hgnc: String,
+    pub previous_symbol: String,
+    pub requested_symbol: String,
+    pub transcripts: Vec<Gene2TranscriptsTranscript>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Gene2TranscriptsTranscript {
+    pub annotations: Annotations,
+    pub coding_end: Option<u32>,
+    pub coding_start: Option<u32>,
+    pub description: String,
+    pub genomic_spans: HashMap<String, serde_json::Value>, // Uncertain format
+    pub length: u32,
+    pub reference: String,
+    pub translation: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(default)]
 pub struct Metadata {
@@ -130,3 +365,255 @@ pub struct Metadata {
     pub vvseqrepo_db: String,
     pub vvta_version: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hgvs::test_utils::{SingleVariantResponseBuilder, load_response};
+    use rstest::rstest;
+    use serde_json::json;
+
+    fn two_variant_response() -> VariantValidatorResponse {
+        serde_json::from_value(json!({
+            "NM_001173464.1:c.2860C>T": {
+                "gene_symbol": "KIF21A",
+                "submitted_variant": "NM_001173464.1:c.2860C>T"
+            },
+            "NM_017641.4:c.2839C>T": {
+                "gene_symbol": "KIF21A",
+                "submitted_variant": "NM_001173464.1:c.2860C>T"
+            },
+            "flag": "gene_variant",
+            "metadata": {"vvdb_version": "vvdb_2025_3"}
+        }))
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_into_all_responses() {
+        let responses = two_variant_response().into_all_responses();
+
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0].variant_info.gene_symbol, "KIF21A");
+        assert_eq!(responses[1].flag, "gene_variant");
+        assert_eq!(responses[1].metadata.vvdb_version, "vvdb_2025_3");
+    }
+
+    #[rstest]
+    fn test_single_variant_response_try_from_err() {
+        let result = SingleVariantResponse::try_from(two_variant_response());
+        assert!(matches!(
+            result,
+            Err(HGVSError::WrongNumberOfVariantInfos { n_found: 2 })
+        ));
+    }
+
+    #[rstest]
+    fn test_available_assemblies() {
+        let response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+
+        assert_eq!(
+            response.available_assemblies(),
+            vec!["grch37", "grch38", "hg19", "hg38"]
+        );
+    }
+
+    #[rstest]
+    fn test_lovd_accessors() {
+        let response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+        assert!(response.lovd_corrections().is_none());
+        assert!(response.lovd_messages().is_none());
+
+        let mut response = serde_json::to_value(response).unwrap();
+        response["variant_info"]["lovd_corrections"] = json!({"NM_001173464.1:c.2860C>T": 1});
+        response["variant_info"]["lovd_messages"] =
+            json!({"ISOURCE": "LOVD", "LIBRARYVERSION": "2025-01-01"});
+        let response: SingleVariantResponse = serde_json::from_value(response).unwrap();
+
+        assert_eq!(
+            response.lovd_corrections().unwrap()["NM_001173464.1:c.2860C>T"],
+            1
+        );
+        assert_eq!(response.lovd_messages().unwrap().i_source, "LOVD");
+        assert_eq!(
+            response.lovd_messages().unwrap().library_version,
+            "2025-01-01"
+        );
+    }
+
+    #[rstest]
+    fn test_abbreviate_dual() {
+        let response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+
+        let dual_assembly_variant = response
+            .abbreviate_dual(GenomeAssembly::Hg38, GenomeAssembly::Hg19)
+            .unwrap();
+
+        let primary = dual_assembly_variant.primary();
+        assert_eq!(primary.assembly(), "hg38");
+        assert_eq!(primary.position(), 39332405);
+        assert_eq!(primary.transcript_hgvs(), "NM_001173464.1:c.2860C>T");
+        assert_eq!(dual_assembly_variant.secondary_assembly(), "hg19");
+        assert_eq!(
+            dual_assembly_variant.secondary_vcf_coordinates(),
+            (
+                "chr12".to_string(),
+                39726207,
+                "G".to_string(),
+                "A".to_string()
+            )
+        );
+        assert_eq!(
+            dual_assembly_variant.secondary_g_hgvs(),
+            "NC_000012.11:g.39726207G>A"
+        );
+    }
+
+    #[rstest]
+    fn test_abbreviate() {
+        let response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+
+        let hgvs_variant = response.abbreviate(GenomeAssembly::Hg19).unwrap();
+
+        assert_eq!(hgvs_variant.assembly(), "hg19");
+        assert_eq!(hgvs_variant.position(), 39726207);
+    }
+
+    #[rstest]
+    fn test_abbreviate_grch38_only() {
+        let response = SingleVariantResponseBuilder::default()
+            .locus(
+                "grch38",
+                "12",
+                39332405,
+                "G",
+                "A",
+                "NC_000012.12:g.39332405G>A",
+            )
+            .build();
+
+        let hgvs_variant = response.abbreviate(GenomeAssembly::Hg38).unwrap();
+
+        assert_eq!(hgvs_variant.assembly(), "hg38");
+        assert_eq!(hgvs_variant.chr(), "12");
+        assert_eq!(hgvs_variant.position(), 39332405);
+    }
+
+    #[rstest]
+    fn test_abbreviate_single_assembly_present() {
+        let response = SingleVariantResponseBuilder::default().build();
+
+        let result = response.abbreviate(GenomeAssembly::Hg19);
+
+        match result {
+            Err(HGVSError::GenomeAssemblyMismatch {
+                desired_assembly,
+                available_assembly,
+                selected_assembly,
+                ..
+            }) => {
+                assert_eq!(desired_assembly, "hg19");
+                assert_eq!(available_assembly, "hg38");
+                assert_eq!(selected_assembly, "hg38");
+            }
+            other => panic!("Expected GenomeAssemblyMismatch, got {other:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_abbreviate_with_fallback() {
+        let response = SingleVariantResponseBuilder::default()
+            .locus(
+                "grch38",
+                "12",
+                39332405,
+                "G",
+                "A",
+                "NC_000012.12:g.39332405G>A",
+            )
+            .build();
+
+        let hgvs_variant = response
+            .abbreviate_with_fallback(&[GenomeAssembly::Hg38, GenomeAssembly::Hg19])
+            .unwrap();
+
+        assert_eq!(hgvs_variant.assembly(), "hg38");
+        assert_eq!(hgvs_variant.chr(), "12");
+        assert_eq!(hgvs_variant.position(), 39332405);
+    }
+
+    #[rstest]
+    fn test_abbreviate_with_fallback_order() {
+        let response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+
+        let hgvs_variant = response
+            .abbreviate_with_fallback(&[GenomeAssembly::Hg19, GenomeAssembly::Hg38])
+            .unwrap();
+
+        assert_eq!(hgvs_variant.assembly(), "hg19");
+    }
+
+    #[rstest]
+    fn test_abbreviate_with_fallback_none_present_err() {
+        let response = SingleVariantResponseBuilder::default().build();
+
+        let result = response.abbreviate_with_fallback(&[GenomeAssembly::Hg19]);
+
+        assert!(matches!(
+            result,
+            Err(HGVSError::GenomeAssemblyNotFound { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case("kif21a", true)]
+    #[case("kif21a_transcript_version_warning", false)]
+    fn test_is_clean(#[case] fixture: &str, #[case] expected: bool) {
+        let response = SingleVariantResponse::try_from(load_response(fixture)).unwrap();
+        assert_eq!(response.is_clean(), expected);
+    }
+
+    #[rstest]
+    #[case("kif21a")]
+    #[case("kif21a_transcript_version_warning")]
+    fn test_is_clean_same_as_abbreviated(#[case] fixture: &str) {
+        let response = SingleVariantResponse::try_from(load_response(fixture)).unwrap();
+        let hgvs_variant = response.abbreviate(GenomeAssembly::Hg38).unwrap();
+        assert_eq!(response.is_clean(), hgvs_variant.is_clean());
+    }
+
+    #[rstest]
+    fn test_is_clean_mitochondrial() {
+        let response = SingleVariantResponseBuilder::default()
+            .flag("mitochondrial")
+            .build();
+        assert!(response.is_clean());
+        assert!(
+            response
+                .abbreviate(GenomeAssembly::Hg38)
+                .unwrap()
+                .is_clean()
+        );
+    }
+
+    #[rstest]
+    #[case(json!({"alt": "A", "chr": "chr12", "pos": "39332405", "ref": "G"}))]
+    #[case(json!({"alt": "A", "chr": "chr12", "pos": 39332405, "ref": "G"}))]
+    fn test_vcf_coordinates_pos(#[case] vcf: serde_json::Value) {
+        let vcf_coordinates: VcfCoordinates = serde_json::from_value(vcf).unwrap();
+        assert_eq!(vcf_coordinates.pos, "39332405");
+    }
+
+    #[rstest]
+    fn test_single_variant_response_serde_round_trip() {
+        let response = SingleVariantResponse::try_from(load_response("kif21a")).unwrap();
+
+        let serialized = serde_json::to_value(&response).unwrap();
+        let deserialized: SingleVariantResponse =
+            serde_json::from_value(serialized.clone()).unwrap();
+
+        assert_eq!(deserialized.variant_info.gene_symbol, "KIF21A");
+        assert_eq!(deserialized.flag, "gene_variant");
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), serialized);
+    }
+}
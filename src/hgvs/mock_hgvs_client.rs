@@ -0,0 +1,156 @@
+use crate::hgvs::enums::GenomeAssembly;
+use crate::hgvs::error::HGVSError;
+use crate::hgvs::hgvs_variant::HgvsVariant;
+use crate::hgvs::traits::HGVSData;
+use std::collections::HashMap;
+
+/// A Mock client for the HGVS interface.
+///
+/// This struct is intended for use in unit testing. Instead of making live HTTP
+/// requests to the VariantValidator API, it serves data from an internal `HashMap`.
+/// This allows for deterministic testing of components that rely on `HGVSData`.
+#[derive(Debug)]
+pub struct MockHGVSClient {
+    variants: HashMap<String, HgvsVariant>,
+}
+
+impl MockHGVSClient {
+    pub fn new(variants: HashMap<String, HgvsVariant>) -> MockHGVSClient {
+        MockHGVSClient { variants }
+    }
+}
+
+impl HGVSData for MockHGVSClient {
+    fn request_and_validate_hgvs(&self, unvalidated_hgvs: &str) -> Result<HgvsVariant, HGVSError> {
+        self.variants
+            .get(unvalidated_hgvs)
+            .cloned()
+            .ok_or_else(|| HGVSError::InvalidHgvs {
+                hgvs: unvalidated_hgvs.to_string(),
+                problems: vec!["HGVS string not found in MockHGVSClient.".to_string()],
+            })
+    }
+
+    fn genome_assembly(&self) -> &GenomeAssembly {
+        &GenomeAssembly::Hg38
+    }
+}
+
+impl Default for MockHGVSClient {
+    fn default() -> Self {
+        let mut variants = HashMap::new();
+        variants.insert(
+            "NM_001173464.1:c.2860C>T".to_string(),
+            HgvsVariant::new(
+                "hg38",
+                "chr12",
+                39332405,
+                "G",
+                "A",
+                "KIF21A",
+                "HGNC:19349",
+                "NM_001173464.1",
+                "c.2860C>T",
+                "NM_001173464.1:c.2860C>T",
+                "NC_000012.12:g.39332405G>A",
+                Some("NP_001166935.1:p.(Arg954Trp)"),
+            ),
+        );
+
+        variants.insert(
+            "NR_002196.1:n.601G>T".to_string(),
+            HgvsVariant::new(
+                "hg38",
+                "chr11",
+                1997235,
+                "C",
+                "A",
+                "H19",
+                "HGNC:4713",
+                "NR_002196.1",
+                "n.601G>T",
+                "NR_002196.1:n.601G>T",
+                "NC_000011.10:g.1997235C>A",
+                None::<&str>,
+            ),
+        );
+
+        MockHGVSClient::new(variants)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hgvs::enums::{AlleleCount, ChromosomalSex};
+    use phenopackets::schema::v2::core::genomic_interpretation::{Call, InterpretationStatus};
+
+    #[test]
+    fn test_request_and_validate_hgvs_success() {
+        let mock = MockHGVSClient::default();
+
+        let hgvs_variant = mock
+            .request_and_validate_hgvs("NR_002196.1:n.601G>T")
+            .unwrap();
+
+        assert_eq!(hgvs_variant.gene_symbol(), "H19");
+    }
+
+    #[test]
+    fn test_request_and_validate_hgvs_not_found() {
+        let mock = MockHGVSClient::default();
+
+        let result = mock.request_and_validate_hgvs("NM_001173464.1:c.2861C>T");
+
+        assert!(matches!(result, Err(HGVSError::InvalidHgvs { .. })));
+    }
+
+    #[test]
+    fn test_create_genomic_interpretation() {
+        let mock = MockHGVSClient::default();
+
+        let genomic_interpretation = mock
+            .create_genomic_interpretation(
+                "P001",
+                "NM_001173464.1:c.2860C>T",
+                AlleleCount::Single,
+                ChromosomalSex::XX,
+                GenomeAssembly::Hg38,
+            )
+            .unwrap();
+
+        assert_eq!(genomic_interpretation.subject_or_biosample_id, "P001");
+        assert_eq!(
+            genomic_interpretation.interpretation_status,
+            i32::from(InterpretationStatus::Causative)
+        );
+        let Some(Call::VariantInterpretation(variant_interpretation)) = genomic_interpretation.call
+        else {
+            panic!("Expected a VariantInterpretation call");
+        };
+        let vcf_record = variant_interpretation
+            .variation_descriptor
+            .unwrap()
+            .vcf_record
+            .unwrap();
+        assert_eq!(vcf_record.pos, 39332405);
+    }
+
+    #[test]
+    fn test_create_genomic_interpretation_other_assembly_err() {
+        let mock = MockHGVSClient::default();
+
+        let result = mock.create_genomic_interpretation(
+            "P001",
+            "NM_001173464.1:c.2860C>T",
+            AlleleCount::Single,
+            ChromosomalSex::XX,
+            GenomeAssembly::Hg19,
+        );
+
+        assert!(matches!(
+            result,
+            Err(HGVSError::GenomeAssemblyNotFound { .. })
+        ));
+    }
+}
@@ -6,19 +6,48 @@
 //!
 //! A struct containing data on the genome assembly, chromosome, position, reference and alt bases of the variant, alongside the symbol and ID of the relevant gene, as well HGVS strings in various format for the variant.
 //!
+//! Whether the transcript of a HgvsVariant is e.g. the MANE Select transcript of its gene is given by its [`TranscriptSelection`].
+//!
+//! # [`DualAssemblyVariant`]
+//!
+//! A HgvsVariant together with its locus on a second genome assembly, created with `SingleVariantResponse::abbreviate_dual`.
+//!
+//! # [`VariantValidatorResponse`]
+//!
+//! The full response from VariantValidator. Use `into_all_responses()` to split it into a [`SingleVariantResponse`] for each variant it has information on.
+//!
 //! # [`HGVSData`]
 //!
-//! A trait consisting of the following method:
+//! A trait consisting of the following methods:
 //!
 //! - `request_and_validate_hgvs(&self, unvalidated_hgvs: &str) -> Result<HgvsVariant, HGVSError>` — validates that the hgvs is accurate and, if so, returns a HgvsVariant object.
+//! - `genome_assembly(&self) -> &GenomeAssembly` — the genome assembly on which variants are requested.
+//! - `get_vcf_coordinates(&self, unvalidated_hgvs: &str, assembly: GenomeAssembly) -> Result<(String, u32, String, String), HGVSError>` — the (chr, pos, ref, alt) of the variant on the given assembly.
+//! - `create_genomic_interpretation(&self, patient_id: &str, unvalidated_hgvs: &str, allele_count: AlleleCount, sex: ChromosomalSex, assembly: GenomeAssembly) -> Result<GenomicInterpretation, HGVSError>` — validates the variant and creates a Phenopacket GenomicInterpretation of it for the patient.
 //!
 //! # [`HGVSClient`]
 //!
 //! The basic implementation of the HGVSData trait. Make a request to the VariantValidator API and receive a HgvsVariant object if the &str was a valid hgvs.c or hgvs.n variant string.
 //!
+//! Note: each HGVSClient makes its requests with a reqwest Client, which holds a connection pool. If several clients are needed, it is recommended to share one reqwest Client between them using `HGVSClient::with_shared_client`.
+//!
+//! To also see any advisory warnings VariantValidator gave for a successfully validated variant (e.g. that a more recent transcript version is available), use `request_and_validate_hgvs_with_diagnostics`, which also returns a [`ValidationWarning`] for each of them. The fatal problems VariantValidator found with a variant that could not be validated are in its `HGVSError::InvalidHgvs` error. [`Diagnostics`] separates the two, and can be taken from a HgvsVariant with `diagnostics` or from an error with `Diagnostics::from_error`. Alternatively, a client created with `with_strict(true)` treats a variant on an outdated transcript version as an error.
+//!
+//! Requests are rate limited, and by default wait until the rate limit allows them. To instead get a `HGVSError::RateLimited` straight away (e.g. in an interactive application), use `try_request_and_validate_hgvs`.
+//!
+//! A genomic HGVS string (e.g. NC_000012.12:g.39332405G>A) can be validated with `validate_genomic`, which returns a HgvsVariant for every transcript overlapping the variant, MANE Select transcripts first. Similarly, `request_all_transcripts` returns a HgvsVariant for a transcript variant projected onto every transcript of its gene.
+//!
+//! The HGVSClient can also list the transcripts of a gene with `gene_transcripts`, which returns a [`TranscriptInfo`] for each transcript, so that a transcript (e.g. the MANE Select transcript) can be chosen for constructing an HGVS string.
+//!
 //! # [`CachedHGVSClient`]
 //!
 //! A cached implementation of the HGVSData trait. The HgvsVariant objects will be cached and can thereafter be accessed without an API call.
+//! Variants are cached per genome assembly, so a cache can be shared between clients using different assemblies.
+//! Every cached variant can be read back out with `all_cached_variants`.
+//!
+//! # [`MockHGVSClient`]
+//!
+//! A mocked implementation of the HGVSData trait for tests and CI.
 //!
 //! # [`AlleleCount`]
 //!
@@ -76,18 +105,42 @@
 //! ```
 
 pub use cached_hgvs_client::CachedHGVSClient;
+pub use diagnostics::Diagnostics;
+pub use dual_assembly_variant::DualAssemblyVariant;
 pub use enums::AlleleCount;
+pub use enums::ChrNaming;
 pub use enums::ChromosomalSex;
+pub use enums::GenomeAssembly;
+pub use enums::HgvsSyntax;
+pub use enums::ResponseFlag;
+pub use enums::VariantClass;
 pub use error::HGVSError;
+pub use genomic_locus::GenomicLocus;
 pub use hgvs_client::HGVSClient;
 pub use hgvs_variant::HgvsVariant;
+pub use json_schema::SingleVariantInfo;
+pub use json_schema::SingleVariantResponse;
+pub use json_schema::VariantValidatorResponse;
+pub use mock_hgvs_client::MockHGVSClient;
 pub use traits::HGVSData;
+pub use transcript_info::TranscriptInfo;
+pub use transcript_selection::TranscriptSelection;
+pub use validation_warning::ValidationWarning;
 
 mod cached_hgvs_client;
+mod diagnostics;
+mod dual_assembly_variant;
 mod enums;
 mod error;
+mod genomic_locus;
 mod hgvs_client;
 mod hgvs_variant;
 mod json_schema;
+mod mock_hgvs_client;
+#[cfg(test)]
+mod test_utils;
 mod traits;
+mod transcript_info;
+mod transcript_selection;
 mod utils;
+mod validation_warning;
@@ -0,0 +1,139 @@
+use crate::hgvs::json_schema::{
+    GeneIds, Metadata, PredictedProteinConsequence, PrimaryAssemblyLoci, SingleVariantInfo,
+    SingleVariantResponse, VariantValidatorResponse, VcfCoordinates,
+};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Loads a recorded VariantValidator response from tests/fixtures/{name}.json.
+pub(crate) fn load_response(name: &str) -> VariantValidatorResponse {
+    load_fixture(name)
+}
+
+/// Loads any recorded JSON payload from tests/fixtures/{name}.json.
+pub(crate) fn load_fixture<T: DeserializeOwned>(name: &str) -> T {
+    let fixture_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(format!("{name}.json"));
+    let fixture = fs::read_to_string(&fixture_path)
+        .unwrap_or_else(|err| panic!("Could not read fixture {fixture_path:?}: {err}"));
+    serde_json::from_str(&fixture)
+        .unwrap_or_else(|err| panic!("Could not deserialize fixture {fixture_path:?}: {err}"))
+}
+
+/// Builds a SingleVariantResponse for a variant on a single assembly, so that tests only need to set the fields they vary.
+///
+/// By default, the response is for NM_001173464.1:c.2860C>T in KIF21A on hg38.
+pub(crate) struct SingleVariantResponseBuilder {
+    transcript_hgvs: String,
+    assembly: String,
+    chr: String,
+    pos: u32,
+    reference: String,
+    alt: String,
+    g_hgvs: String,
+    gene_symbol: String,
+    hgnc_id: String,
+    p_hgvs: String,
+    flag: String,
+}
+
+impl Default for SingleVariantResponseBuilder {
+    fn default() -> Self {
+        SingleVariantResponseBuilder {
+            transcript_hgvs: "NM_001173464.1:c.2860C>T".to_string(),
+            assembly: "hg38".to_string(),
+            chr: "chr12".to_string(),
+            pos: 39332405,
+            reference: "G".to_string(),
+            alt: "A".to_string(),
+            g_hgvs: "NC_000012.12:g.39332405G>A".to_string(),
+            gene_symbol: "KIF21A".to_string(),
+            hgnc_id: "HGNC:19349".to_string(),
+            p_hgvs: "NP_001166935.1:p.(Arg954Trp)".to_string(),
+            flag: "gene_variant".to_string(),
+        }
+    }
+}
+
+impl SingleVariantResponseBuilder {
+    pub(crate) fn transcript_hgvs(mut self, transcript_hgvs: &str) -> Self {
+        self.transcript_hgvs = transcript_hgvs.to_string();
+        self
+    }
+
+    /// Sets the only primary assembly locus of the response.
+    pub(crate) fn locus(
+        mut self,
+        assembly: &str,
+        chr: &str,
+        pos: u32,
+        reference: &str,
+        alt: &str,
+        g_hgvs: &str,
+    ) -> Self {
+        self.assembly = assembly.to_string();
+        self.chr = chr.to_string();
+        self.pos = pos;
+        self.reference = reference.to_string();
+        self.alt = alt.to_string();
+        self.g_hgvs = g_hgvs.to_string();
+        self
+    }
+
+    pub(crate) fn gene_symbol(mut self, gene_symbol: &str) -> Self {
+        self.gene_symbol = gene_symbol.to_string();
+        self
+    }
+
+    pub(crate) fn hgnc_id(mut self, hgnc_id: &str) -> Self {
+        self.hgnc_id = hgnc_id.to_string();
+        self
+    }
+
+    /// An empty p_hgvs means that there is no predicted protein consequence.
+    pub(crate) fn p_hgvs(mut self, p_hgvs: &str) -> Self {
+        self.p_hgvs = p_hgvs.to_string();
+        self
+    }
+
+    pub(crate) fn flag(mut self, flag: &str) -> Self {
+        self.flag = flag.to_string();
+        self
+    }
+
+    pub(crate) fn build(self) -> SingleVariantResponse {
+        let locus = PrimaryAssemblyLoci {
+            hgvs_genomic_description: self.g_hgvs,
+            vcf: VcfCoordinates {
+                alt: self.alt,
+                chr: self.chr,
+                pos: self.pos.to_string(),
+                reference: self.reference,
+            },
+        };
+        SingleVariantResponse {
+            variant_info: SingleVariantInfo {
+                gene_ids: GeneIds {
+                    hgnc_id: self.hgnc_id,
+                    ..Default::default()
+                },
+                gene_symbol: self.gene_symbol,
+                hgvs_predicted_protein_consequence: PredictedProteinConsequence {
+                    tlr: self.p_hgvs,
+                    ..Default::default()
+                },
+                hgvs_transcript_variant: self.transcript_hgvs.clone(),
+                primary_assembly_loci: HashMap::from([(self.assembly.clone(), locus)]),
+                selected_assembly: self.assembly,
+                submitted_variant: self.transcript_hgvs,
+                ..Default::default()
+            },
+            flag: self.flag,
+            metadata: Metadata::default(),
+        }
+    }
+}
@@ -1,9 +1,111 @@
 #![allow(unused)]
 
+use crate::hgvs::enums::{AlleleCount, ChrNaming, ChromosomalSex, GenomeAssembly};
 use crate::hgvs::error::HGVSError;
 use crate::hgvs::hgvs_variant::HgvsVariant;
+use phenopackets::schema::v2::core::GenomicInterpretation;
+use phenopackets::schema::v2::core::genomic_interpretation::{Call, InterpretationStatus};
 use std::fmt::Debug;
 
 pub trait HGVSData: Debug {
     fn request_and_validate_hgvs(&self, unvalidated_hgvs: &str) -> Result<HgvsVariant, HGVSError>;
+
+    /// The genome assembly on which variants are requested.
+    ///
+    /// By default, this is hg38, so implementors which request variants on another assembly must override it.
+    fn genome_assembly(&self) -> &GenomeAssembly {
+        &GenomeAssembly::Hg38
+    }
+
+    /// How the chromosomes of the requested variants are named. By default, they are named as returned by VariantValidator.
+    fn chr_naming(&self) -> ChrNaming {
+        ChrNaming::AsReturned
+    }
+
+    /// Validates the HGVS string and returns its VCF coordinates (chr, pos, ref, alt) on the given assembly.
+    ///
+    /// By default, only the assembly of the implementor is supported.
+    fn get_vcf_coordinates(
+        &self,
+        unvalidated_hgvs: &str,
+        assembly: GenomeAssembly,
+    ) -> Result<(String, u32, String, String), HGVSError> {
+        let hgvs_variant = self.request_and_validate_hgvs(unvalidated_hgvs)?;
+        if hgvs_variant.assembly() != assembly.to_string() {
+            return Err(HGVSError::GenomeAssemblyNotFound {
+                hgvs: unvalidated_hgvs.to_string(),
+                desired_assembly: assembly.to_string(),
+                found_assemblies: vec![hgvs_variant.assembly().to_string()],
+            });
+        }
+        Ok(hgvs_variant.vcf_coordinates())
+    }
+
+    /// Validates the HGVS string and creates a causative GenomicInterpretation of the variant for the patient,
+    /// whose VariantInterpretation is created from the allele count and chromosomal sex.
+    ///
+    /// By default, only the assembly of the implementor is supported.
+    fn create_genomic_interpretation(
+        &self,
+        patient_id: &str,
+        unvalidated_hgvs: &str,
+        allele_count: AlleleCount,
+        sex: ChromosomalSex,
+        assembly: GenomeAssembly,
+    ) -> Result<GenomicInterpretation, HGVSError> {
+        let hgvs_variant = self.request_and_validate_hgvs(unvalidated_hgvs)?;
+        if hgvs_variant.assembly_enum() != Some(assembly) {
+            return Err(HGVSError::GenomeAssemblyNotFound {
+                hgvs: unvalidated_hgvs.to_string(),
+                desired_assembly: assembly.to_string(),
+                found_assemblies: vec![hgvs_variant.assembly().to_string()],
+            });
+        }
+        let variant_interpretation =
+            hgvs_variant.create_variant_interpretation(allele_count, &sex)?;
+
+        Ok(GenomicInterpretation {
+            subject_or_biosample_id: patient_id.to_string(),
+            interpretation_status: InterpretationStatus::Causative.into(),
+            call: Some(Call::VariantInterpretation(variant_interpretation)),
+        })
+    }
+}
+
+impl<T: HGVSData + ?Sized> HGVSData for Box<T> {
+    fn request_and_validate_hgvs(&self, unvalidated_hgvs: &str) -> Result<HgvsVariant, HGVSError> {
+        (**self).request_and_validate_hgvs(unvalidated_hgvs)
+    }
+
+    fn genome_assembly(&self) -> &GenomeAssembly {
+        (**self).genome_assembly()
+    }
+
+    fn chr_naming(&self) -> ChrNaming {
+        (**self).chr_naming()
+    }
+
+    fn get_vcf_coordinates(
+        &self,
+        unvalidated_hgvs: &str,
+        assembly: GenomeAssembly,
+    ) -> Result<(String, u32, String, String), HGVSError> {
+        (**self).get_vcf_coordinates(unvalidated_hgvs, assembly)
+    }
+    fn create_genomic_interpretation(
+        &self,
+        patient_id: &str,
+        unvalidated_hgvs: &str,
+        allele_count: AlleleCount,
+        sex: ChromosomalSex,
+        assembly: GenomeAssembly,
+    ) -> Result<GenomicInterpretation, HGVSError> {
+        (**self).create_genomic_interpretation(
+            patient_id,
+            unvalidated_hgvs,
+            allele_count,
+            sex,
+            assembly,
+        )
+    }
 }
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// A transcript of a gene, as listed by the VariantValidator gene2transcripts endpoint.
+#[derive(Clone, Debug, Serialize, Deserialize, Default, PartialEq)]
+pub struct TranscriptInfo {
+    /// Transcript accession, e.g., NM_017641.4
+    accession: String,
+    /// Whether the transcript is the MANE Select transcript of the gene
+    is_mane_select: bool,
+    /// Length of the transcript in bases
+    length: u32,
+}
+
+impl TranscriptInfo {
+    pub fn new(accession: impl Into<String>, is_mane_select: bool, length: u32) -> Self {
+        TranscriptInfo {
+            accession: accession.into(),
+            is_mane_select,
+            length,
+        }
+    }
+
+    pub fn accession(&self) -> &str {
+        self.accession.as_ref()
+    }
+
+    pub fn is_mane_select(&self) -> bool {
+        self.is_mane_select
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+}
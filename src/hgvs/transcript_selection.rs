@@ -0,0 +1,53 @@
+use crate::hgvs::json_schema::Annotations;
+use serde::{Deserialize, Serialize};
+
+/// Which transcript selection sets the transcript of a variant belongs to, according to VariantValidator.
+///
+/// This helps to understand why VariantValidator chose the transcript of a variant.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptSelection {
+    /// Whether the transcript is the RefSeq Select transcript of the gene
+    refseq_select: bool,
+    /// Whether the transcript is the Ensembl canonical transcript of the gene
+    ensembl_select: bool,
+    /// Whether the transcript is the MANE Select transcript of the gene
+    mane_select: bool,
+}
+
+impl TranscriptSelection {
+    pub fn new(refseq_select: bool, ensembl_select: bool, mane_select: bool) -> Self {
+        TranscriptSelection {
+            refseq_select,
+            ensembl_select,
+            mane_select,
+        }
+    }
+
+    pub fn refseq_select(&self) -> bool {
+        self.refseq_select
+    }
+
+    pub fn ensembl_select(&self) -> bool {
+        self.ensembl_select
+    }
+
+    pub fn mane_select(&self) -> bool {
+        self.mane_select
+    }
+
+    /// Whether the transcript is in any of the selection sets.
+    pub fn is_selected(&self) -> bool {
+        self.refseq_select || self.ensembl_select || self.mane_select
+    }
+}
+
+impl From<&Annotations> for TranscriptSelection {
+    fn from(annotations: &Annotations) -> Self {
+        TranscriptSelection::new(
+            annotations.refseq_select,
+            annotations.ensembl_select,
+            annotations.mane_select,
+        )
+    }
+}
@@ -1,3 +1,5 @@
+use crate::hgvs::error::HGVSError;
+
 pub fn is_c_hgvs(allele: &str) -> bool {
     allele.starts_with("c.")
 }
@@ -9,3 +11,35 @@ pub fn is_n_hgvs(allele: &str) -> bool {
 pub fn is_m_hgvs(allele: &str) -> bool {
     allele.starts_with("m.")
 }
+
+pub fn is_g_hgvs(allele: &str) -> bool {
+    allele.starts_with("g.")
+}
+
+pub fn is_p_hgvs(allele: &str) -> bool {
+    allele.starts_with("p.")
+}
+
+/// VariantValidator uses empty strings for missing elements, which are converted to None here.
+pub(crate) fn non_empty(element: String) -> Option<String> {
+    if element.is_empty() {
+        None
+    } else {
+        Some(element)
+    }
+}
+
+pub(crate) fn get_transcript_and_allele(unvalidated_hgvs: &str) -> Result<(&str, &str), HGVSError> {
+    let split_hgvs = unvalidated_hgvs.split(':').collect::<Vec<&str>>();
+    let colon_count = split_hgvs.len() - 1;
+    if colon_count != 1 {
+        Err(HGVSError::HgvsFormatNotAccepted {
+            hgvs: unvalidated_hgvs.to_string(),
+            problem: "There must be exactly one colon in a HGVS string.".to_string(),
+        })
+    } else {
+        let transcript = split_hgvs[0];
+        let allele = split_hgvs[1];
+        Ok((transcript, allele))
+    }
+}
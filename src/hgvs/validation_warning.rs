@@ -0,0 +1,133 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// An advisory warning from VariantValidator about a variant that was nonetheless validated successfully.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ValidationWarning {
+    /// The kind of warning, e.g., TranscriptVersionWarning, if VariantValidator gave one
+    category: Option<String>,
+    /// The full text of the warning
+    message: String,
+}
+
+impl ValidationWarning {
+    /// Parses a warning of the form "TranscriptVersionWarning: A more recent version of ... is available".
+    pub fn new(warning: impl Into<String>) -> Self {
+        let message = warning.into();
+        let category = message
+            .split_once(':')
+            .map(|(category, _)| category.trim())
+            .filter(|category| {
+                !category.is_empty() && category.chars().all(|c| c.is_ascii_alphanumeric())
+            })
+            .map(str::to_string);
+        ValidationWarning { category, message }
+    }
+
+    pub fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    pub fn message(&self) -> &str {
+        self.message.as_ref()
+    }
+
+    /// The (submitted, actual) reference bases, if VariantValidator warned that the submitted reference base is wrong,
+    /// e.g. "NM_001173464.1:c.2860G>T: Variant reference (G) does not agree with reference sequence (C)".
+    pub fn reference_base_mismatch(&self) -> Option<(String, String)> {
+        let mismatch_regex = Regex::new(
+            r"Variant reference \((\w+)\) does not agree with reference sequence \((\w+)\)",
+        )
+        .unwrap();
+        let captures = mismatch_regex.captures(&self.message)?;
+        Some((captures[1].to_string(), captures[2].to_string()))
+    }
+
+    /// Whether VariantValidator warned that a more recent version of the transcript is available.
+    pub fn is_transcript_version_warning(&self) -> bool {
+        self.category() == Some("TranscriptVersionWarning")
+    }
+
+    /// The more recent transcript version, if this is a TranscriptVersionWarning,
+    /// e.g. NM_001173464.2 from "... NM_001173464.1 is available (NM_001173464.2)".
+    pub fn latest_transcript(&self) -> Option<&str> {
+        if !self.is_transcript_version_warning() {
+            return None;
+        }
+        self.message
+            .rsplit_once('(')
+            .and_then(|(_, latest)| latest.split_once(')'))
+            .map(|(latest, _)| latest)
+    }
+
+    /// The warning as a JSON object with its kind (the category, or null) and message,
+    /// and any fields extracted from the message (latest_transcript, or submitted_ref and actual_ref).
+    pub fn to_json(&self) -> Value {
+        let mut warning_json = json!({
+            "kind": self.category,
+            "message": self.message,
+        });
+        if let Some(latest_transcript) = self.latest_transcript() {
+            warning_json["latest_transcript"] = json!(latest_transcript);
+        }
+        if let Some((submitted_ref, actual_ref)) = self.reference_base_mismatch() {
+            warning_json["submitted_ref"] = json!(submitted_ref);
+            warning_json["actual_ref"] = json!(actual_ref);
+        }
+        warning_json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "TranscriptVersionWarning: A more recent version of the selected reference sequence NM_001173464.1 is available (NM_001173464.2)",
+        Some("TranscriptVersionWarning")
+    )]
+    #[case(
+        "NM_001173464.1:c.2860C>T auto-mapped to NM_001173464.1:c.2860C>T",
+        None
+    )]
+    #[case("No warning category given", None)]
+    fn test_validation_warning_category(#[case] warning: &str, #[case] expected: Option<&str>) {
+        let validation_warning = ValidationWarning::new(warning);
+
+        assert_eq!(validation_warning.category(), expected);
+        assert_eq!(validation_warning.message(), warning);
+    }
+
+    #[rstest]
+    fn test_reference_base_mismatch() {
+        let validation_warning = ValidationWarning::new(
+            "NM_001173464.1:c.2860G>T: Variant reference (G) does not agree with reference sequence (C)",
+        );
+        assert_eq!(
+            validation_warning.reference_base_mismatch(),
+            Some(("G".to_string(), "C".to_string()))
+        );
+
+        let validation_warning = ValidationWarning::new(
+            "TranscriptVersionWarning: A more recent version of the selected reference sequence NM_001173464.1 is available (NM_001173464.2)",
+        );
+        assert_eq!(validation_warning.reference_base_mismatch(), None);
+    }
+
+    #[rstest]
+    fn test_to_json_reference_base_mismatch() {
+        let validation_warning = ValidationWarning::new(
+            "NM_001173464.1:c.2860G>T: Variant reference (G) does not agree with reference sequence (C)",
+        );
+
+        let warning_json = validation_warning.to_json();
+
+        assert_eq!(warning_json["kind"], Value::Null);
+        assert_eq!(warning_json["submitted_ref"], "G");
+        assert_eq!(warning_json["actual_ref"], "C");
+        assert!(warning_json.get("latest_transcript").is_none());
+    }
+}
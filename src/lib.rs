@@ -15,8 +15,19 @@
 //! - If you use CachedHGVSClient, the HgvsVariant objects will be cached and can thereafter be accessed without an API call.
 //!
 //! - There is also functionality for creating a Phenopacket VariantInterpretation from a HgvsVariant object and data on allele count and chromosomal sex.
+//!
+//! ## PivotClient
+//!
+//! - A PivotClient holds both an HGNC and an HGVS client, e.g. for validating a variant and checking that it is in a given gene with `validate_variant_in_gene`.
+//!
+//! - It implements both HGNCData and HGVSData, so it can be used wherever either client is needed. Use `PivotClient::builder()` to configure the URLs, rate limiters and caches of the underlying clients.
+
+pub use pivot_client::DynPivotClient;
+pub use pivot_client::PivotClient;
+pub use pivot_client::PivotClientBuilder;
 
 mod caching;
 pub mod hgnc;
 pub mod hgvs;
+mod pivot_client;
 pub(crate) mod utils;
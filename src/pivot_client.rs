@@ -0,0 +1,265 @@
+use crate::hgnc::{CachedHGNCClient, GeneDoc, GeneQuery, HGNCClient, HGNCData, HGNCError};
+use crate::hgvs::{
+    AlleleCount, CachedHGVSClient, ChrNaming, ChromosomalSex, GenomeAssembly, HGVSClient, HGVSData,
+    HGVSError, HgvsVariant,
+};
+use phenopackets::schema::v2::core::GenomicInterpretation;
+use ratelimit::Ratelimiter;
+use reqwest::blocking::Client;
+use std::path::PathBuf;
+
+/// A client for both HGNC and VariantValidator, for workflows which need gene and variant data together.
+///
+/// A PivotClient implements both HGNCData and HGVSData. Use `PivotClient::builder()` to configure the underlying clients.
+#[derive(Debug)]
+pub struct PivotClient<N = HGNCClient, V = HGVSClient> {
+    hgnc_client: N,
+    hgvs_client: V,
+}
+
+impl<N: HGNCData, V: HGVSData> PivotClient<N, V> {
+    pub fn new(hgnc_client: N, hgvs_client: V) -> Self {
+        PivotClient {
+            hgnc_client,
+            hgvs_client,
+        }
+    }
+
+    pub fn hgnc_client(&self) -> &N {
+        &self.hgnc_client
+    }
+
+    pub fn hgvs_client(&self) -> &V {
+        &self.hgvs_client
+    }
+
+    /// Resolves the gene (a symbol or an HGNC ID) on HGNC, validates the HGVS string and checks that the variant is in that gene.
+    pub fn validate_variant_in_gene(
+        &self,
+        hgvs: &str,
+        gene: &str,
+    ) -> Result<HgvsVariant, HGVSError> {
+        let hgnc_id = self.hgnc_client.request_hgnc_id(GeneQuery::from(gene))?;
+        let hgvs_variant = self.hgvs_client.request_and_validate_hgvs(hgvs)?;
+        hgvs_variant.validate_against_gene(&hgnc_id)?;
+        Ok(hgvs_variant)
+    }
+}
+
+impl PivotClient {
+    pub fn builder() -> PivotClientBuilder {
+        PivotClientBuilder::default()
+    }
+}
+
+impl Default for PivotClient {
+    /// An uncached HGNC and HGVS client with the default settings, which share a reqwest Client.
+    fn default() -> Self {
+        let client = Client::new();
+        PivotClient::new(
+            HGNCClient::with_shared_client(client.clone()),
+            HGVSClient::with_shared_client(client),
+        )
+    }
+}
+
+impl<N: HGNCData, V: HGVSData> HGNCData for PivotClient<N, V> {
+    fn request_gene_data(&self, query: GeneQuery) -> Result<GeneDoc, HGNCError> {
+        self.hgnc_client.request_gene_data(query)
+    }
+}
+
+impl<N: HGNCData, V: HGVSData> HGVSData for PivotClient<N, V> {
+    fn request_and_validate_hgvs(&self, unvalidated_hgvs: &str) -> Result<HgvsVariant, HGVSError> {
+        self.hgvs_client.request_and_validate_hgvs(unvalidated_hgvs)
+    }
+
+    fn genome_assembly(&self) -> &GenomeAssembly {
+        self.hgvs_client.genome_assembly()
+    }
+
+    fn chr_naming(&self) -> ChrNaming {
+        self.hgvs_client.chr_naming()
+    }
+
+    fn get_vcf_coordinates(
+        &self,
+        unvalidated_hgvs: &str,
+        assembly: GenomeAssembly,
+    ) -> Result<(String, u32, String, String), HGVSError> {
+        self.hgvs_client
+            .get_vcf_coordinates(unvalidated_hgvs, assembly)
+    }
+
+    fn create_genomic_interpretation(
+        &self,
+        patient_id: &str,
+        unvalidated_hgvs: &str,
+        allele_count: AlleleCount,
+        sex: ChromosomalSex,
+        assembly: GenomeAssembly,
+    ) -> Result<GenomicInterpretation, HGVSError> {
+        self.hgvs_client.create_genomic_interpretation(
+            patient_id,
+            unvalidated_hgvs,
+            allele_count,
+            sex,
+            assembly,
+        )
+    }
+}
+
+/// A PivotClient whose clients were configured at runtime, e.g. by a PivotClientBuilder.
+pub type DynPivotClient = PivotClient<Box<dyn HGNCData>, Box<dyn HGVSData>>;
+
+/// Configures the HGNC and HGVS clients of a PivotClient. Any setting which is not given keeps the default of the client.
+///
+/// If a cache file path is given, then the corresponding client is cached.
+#[derive(Default)]
+pub struct PivotClientBuilder {
+    client: Option<Client>,
+    hgnc_api_url: Option<String>,
+    hgnc_rate_limiter: Option<Ratelimiter>,
+    hgnc_cache_file_path: Option<PathBuf>,
+    hgvs_api_url: Option<String>,
+    hgvs_rate_limiter: Option<Ratelimiter>,
+    hgvs_cache_file_path: Option<PathBuf>,
+}
+
+impl PivotClientBuilder {
+    /// The reqwest Client shared by the HGNC and HGVS clients.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn hgnc_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.hgnc_api_url = Some(api_url.into());
+        self
+    }
+
+    pub fn hgnc_rate_limiter(mut self, rate_limiter: Ratelimiter) -> Self {
+        self.hgnc_rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    pub fn hgnc_cache_file_path(mut self, cache_file_path: impl Into<PathBuf>) -> Self {
+        self.hgnc_cache_file_path = Some(cache_file_path.into());
+        self
+    }
+
+    pub fn hgvs_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.hgvs_api_url = Some(api_url.into());
+        self
+    }
+
+    pub fn hgvs_rate_limiter(mut self, rate_limiter: Ratelimiter) -> Self {
+        self.hgvs_rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    pub fn hgvs_cache_file_path(mut self, cache_file_path: impl Into<PathBuf>) -> Self {
+        self.hgvs_cache_file_path = Some(cache_file_path.into());
+        self
+    }
+
+    pub fn build(self) -> Result<DynPivotClient, HGVSError> {
+        let client = self.client.unwrap_or_default();
+
+        let mut hgnc_client = HGNCClient::with_shared_client(client.clone());
+        if let Some(api_url) = self.hgnc_api_url {
+            hgnc_client = hgnc_client.with_api_url(api_url);
+        }
+        if let Some(rate_limiter) = self.hgnc_rate_limiter {
+            hgnc_client = hgnc_client.with_rate_limiter(rate_limiter);
+        }
+        let hgnc_client: Box<dyn HGNCData> = match self.hgnc_cache_file_path {
+            Some(cache_file_path) => Box::new(CachedHGNCClient::new(cache_file_path, hgnc_client)?),
+            None => Box::new(hgnc_client),
+        };
+
+        let mut hgvs_client = HGVSClient::with_shared_client(client);
+        if let Some(api_url) = self.hgvs_api_url {
+            hgvs_client = hgvs_client.with_api_url(api_url);
+        }
+        if let Some(rate_limiter) = self.hgvs_rate_limiter {
+            hgvs_client = hgvs_client.with_rate_limiter(rate_limiter);
+        }
+        let hgvs_client: Box<dyn HGVSData> = match self.hgvs_cache_file_path {
+            Some(cache_file_path) => Box::new(CachedHGVSClient::new(cache_file_path, hgvs_client)?),
+            None => Box::new(hgvs_client),
+        };
+
+        Ok(PivotClient::new(hgnc_client, hgvs_client))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hgnc::{GeneDoc, MockHGNCClient};
+    use crate::hgvs::MockHGVSClient;
+    use rstest::{fixture, rstest};
+    use std::collections::HashMap;
+
+    #[fixture]
+    fn mock_pivot_client() -> PivotClient<MockHGNCClient, MockHGVSClient> {
+        let kif21a = GeneDoc::default()
+            .with_hgnc_id("HGNC:19349")
+            .with_symbol("KIF21A");
+        let docs = HashMap::from([
+            ("KIF21A".to_string(), kif21a.clone()),
+            ("HGNC:19349".to_string(), kif21a),
+            (
+                "H19".to_string(),
+                GeneDoc::default()
+                    .with_hgnc_id("HGNC:4713")
+                    .with_symbol("H19"),
+            ),
+        ]);
+        PivotClient::new(MockHGNCClient::new(docs), MockHGVSClient::default())
+    }
+
+    #[rstest]
+    fn test_chr_naming_is_forwarded() {
+        let pivot_client = PivotClient::new(
+            HGNCClient::default(),
+            HGVSClient::default().with_chr_naming(ChrNaming::Bare),
+        );
+
+        assert_eq!(pivot_client.chr_naming(), ChrNaming::Bare);
+    }
+
+    #[rstest]
+    #[case("KIF21A")]
+    #[case("HGNC:19349")]
+    fn test_validate_variant_in_gene(
+        mock_pivot_client: PivotClient<MockHGNCClient, MockHGVSClient>,
+        #[case] gene: &str,
+    ) {
+        let hgvs_variant = mock_pivot_client
+            .validate_variant_in_gene("NM_001173464.1:c.2860C>T", gene)
+            .unwrap();
+
+        assert_eq!(hgvs_variant.gene_symbol(), "KIF21A");
+    }
+
+    #[rstest]
+    fn test_validate_variant_in_gene_wrong_gene(
+        mock_pivot_client: PivotClient<MockHGNCClient, MockHGVSClient>,
+    ) {
+        let result = mock_pivot_client.validate_variant_in_gene("NM_001173464.1:c.2860C>T", "H19");
+
+        assert!(matches!(result, Err(HGVSError::MismatchingGeneData { .. })));
+    }
+
+    #[rstest]
+    fn test_validate_variant_in_gene_unknown_gene(
+        mock_pivot_client: PivotClient<MockHGNCClient, MockHGVSClient>,
+    ) {
+        let result =
+            mock_pivot_client.validate_variant_in_gene("NM_001173464.1:c.2860C>T", "KIF21");
+
+        assert!(matches!(result, Err(HGVSError::Hgnc(_))));
+    }
+}
@@ -0,0 +1,71 @@
+use mockito::Matcher;
+use pivot::PivotClient;
+use pivot::hgnc::{GeneQuery, HGNCData};
+use pivot::hgvs::HGVSData;
+use std::fs;
+use std::path::PathBuf;
+
+const KIF21A_HGNC_RESPONSE: &str = r#"{
+    "responseHeader": {"status": 0, "QTime": 1},
+    "response": {
+        "numFound": 1,
+        "start": 0,
+        "numFoundExact": true,
+        "docs": [{"symbol": "KIF21A", "hgnc_id": "HGNC:19349"}]
+    }
+}"#;
+
+fn load_fixture(name: &str) -> String {
+    let fixture_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(format!("{name}.json"));
+    fs::read_to_string(fixture_path).unwrap()
+}
+
+#[test]
+fn test_pivot_client_facade() {
+    let mut hgnc_server = mockito::Server::new();
+    let hgnc_mock = hgnc_server
+        .mock(
+            "GET",
+            Matcher::Regex("^/fetch/(symbol/KIF21A|hgnc_id/HGNC:19349)$".to_string()),
+        )
+        .with_body(KIF21A_HGNC_RESPONSE)
+        .expect(2)
+        .create();
+    let mut hgvs_server = mockito::Server::new();
+    let hgvs_mock = hgvs_server
+        .mock("GET", Matcher::Regex("^/+hg38/NM_001173464.1".to_string()))
+        .with_body(load_fixture("kif21a"))
+        .expect(1)
+        .create();
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let client = PivotClient::builder()
+        .hgnc_api_url(format!("{}/", hgnc_server.url()))
+        .hgvs_api_url(hgvs_server.url())
+        .hgvs_cache_file_path(temp_dir.path().join("cache.hgvs"))
+        .build()
+        .unwrap();
+
+    let (symbol, hgnc_id) = client
+        .request_gene_identifier_pair(GeneQuery::Symbol("KIF21A"))
+        .unwrap();
+    assert_eq!(symbol, "KIF21A");
+    assert_eq!(hgnc_id, "HGNC:19349");
+
+    let hgvs_variant = client
+        .validate_variant_in_gene("NM_001173464.1:c.2860C>T", "HGNC:19349")
+        .unwrap();
+    assert_eq!(hgvs_variant.position(), 39332405);
+
+    // the variant has been cached, so VariantValidator is not requested again
+    let cached_hgvs_variant = client
+        .request_and_validate_hgvs("NM_001173464.1:c.2860C>T")
+        .unwrap();
+    assert_eq!(cached_hgvs_variant, hgvs_variant);
+
+    hgnc_mock.assert();
+    hgvs_mock.assert();
+}